@@ -19,7 +19,9 @@ use num_bigint::BigUint;
 use serde::Deserialize;
 use serde::Serialize;
 use std::fmt::Debug;
+use std::str::FromStr;
 use std::sync::Arc;
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 #[derive(Clone, Copy, Debug, EnumIter, Eq, PartialEq, PartialOrd, Ord, Hash)]
@@ -89,6 +91,41 @@ impl OpcodeClass {
         }
     }
 
+    // Stable name of the class, see `OpcodeClassPlain::as_str`.
+    fn name(&self) -> &'static str {
+        match self {
+            OpcodeClass::LocalGet => "local_get",
+            OpcodeClass::LocalSet => "local_set",
+            OpcodeClass::LocalTee => "local_tee",
+            OpcodeClass::GlobalGet => "global_get",
+            OpcodeClass::GlobalSet => "global_set",
+            OpcodeClass::Const => "const",
+            OpcodeClass::Drop => "drop",
+            OpcodeClass::Select => "select",
+            OpcodeClass::Return => "return",
+            OpcodeClass::Bin => "bin",
+            OpcodeClass::Unary => "unary",
+            OpcodeClass::BinShift => "bin_shift",
+            OpcodeClass::BinBit => "bin_bit",
+            OpcodeClass::Test => "test",
+            OpcodeClass::Rel => "rel",
+            OpcodeClass::Br => "br",
+            OpcodeClass::BrIf => "br_if",
+            OpcodeClass::BrIfEqz => "br_if_eqz",
+            OpcodeClass::BrTable => "br_table",
+            OpcodeClass::Unreachable => "unreachable",
+            OpcodeClass::Call => "call",
+            OpcodeClass::CallHost => "call_host",
+            OpcodeClass::CallIndirect => "call_indirect",
+            OpcodeClass::Load => "load",
+            OpcodeClass::Store => "store",
+            OpcodeClass::MemorySize => "memory_size",
+            OpcodeClass::MemoryGrow => "memory_grow",
+            OpcodeClass::Conversion => "conversion",
+            OpcodeClass::ForeignPluginStart => "foreign_plugin_start",
+        }
+    }
+
     pub fn jops(&self) -> u64 {
         match self {
             OpcodeClass::Return => 1,
@@ -99,6 +136,7 @@ impl OpcodeClass {
     }
 }
 
+// Serialized as its name (see `as_str`), so it's a stable key of json maps.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct OpcodeClassPlain(pub usize);
 
@@ -106,6 +144,80 @@ impl OpcodeClassPlain {
     pub fn index(self) -> usize {
         self.0
     }
+
+    /// Stable name of the class independent of its index, "unknown" if the index matches no
+    /// class.
+    pub fn as_str(&self) -> &'static str {
+        Self::named()
+            .find(|(class, _)| class == self)
+            .map_or("unknown", |(_, name)| name)
+    }
+
+    // Every class with its name, the plugins take the place of ForeignPluginStart.
+    fn named() -> impl Iterator<Item = (OpcodeClassPlain, &'static str)> {
+        let plugins = HostPlugin::iter().map(|plugin| {
+            let name = match plugin {
+                HostPlugin::HostInput => "foreign_host_input",
+                HostPlugin::Context => "foreign_context",
+                HostPlugin::Require => "foreign_require",
+            };
+
+            (
+                OpcodeClassPlain(OpcodeClass::ForeignPluginStart as usize + plugin as usize),
+                name,
+            )
+        });
+
+        plugins.chain(
+            OpcodeClass::iter()
+                .filter(|class| *class != OpcodeClass::ForeignPluginStart)
+                .map(|class| (OpcodeClassPlain(class as usize), class.name())),
+        )
+    }
+
+    /// Every class an event table entry may have: one per opcode class and one per host plugin
+    /// in place of `ForeignPluginStart`. `Unreachable` traps, so it never reaches the trace.
+    pub fn all() -> Vec<OpcodeClassPlain> {
+        OpcodeClass::iter()
+            .filter(|class| {
+                *class != OpcodeClass::Unreachable && *class != OpcodeClass::ForeignPluginStart
+            })
+            .map(|class| OpcodeClassPlain(class as usize))
+            .chain(HostPlugin::iter().map(|plugin| {
+                OpcodeClassPlain(OpcodeClass::ForeignPluginStart as usize + plugin as usize)
+            }))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownOpcodeClass(pub String);
+
+impl FromStr for OpcodeClassPlain {
+    type Err = UnknownOpcodeClass;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::named()
+            .find(|(_, name)| *name == s)
+            .map(|(class, _)| class)
+            .ok_or_else(|| UnknownOpcodeClass(s.to_string()))
+    }
+}
+
+impl Serialize for OpcodeClassPlain {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for OpcodeClassPlain {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+
+        name.parse().map_err(|UnknownOpcodeClass(name)| {
+            serde::de::Error::custom(format!("unknown opcode class {}", name))
+        })
+    }
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
@@ -656,6 +768,13 @@ impl InstructionTable {
         &self.0[fid as usize][iid as usize].as_ref().unwrap()
     }
 
+    pub fn try_get(&self, fid: u32, iid: u32) -> Option<&InstructionTableEntry> {
+        self.0
+            .get(fid as usize)
+            .and_then(|function| function.get(iid as usize))
+            .and_then(|entry| entry.as_ref())
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &InstructionTableEntry> {
         self.0.iter().flatten().flatten().filter_map(|x| Some(x))
     }
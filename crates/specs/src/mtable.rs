@@ -153,3 +153,9 @@ impl MTable {
         MTable(entries)
     }
 }
+
+impl From<Vec<MemoryTableEntry>> for MTable {
+    fn from(entries: Vec<MemoryTableEntry>) -> Self {
+        MTable::new(entries)
+    }
+}
@@ -1,9 +1,12 @@
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::AssignedCell;
 use num_bigint::BigUint;
 use serde::Deserialize;
 use serde::Serialize;
+use std::ops::Range;
 
 #[cfg(feature = "continuation")]
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InitializationState<T, U> {
     pub eid: T,
     pub fid: T,
@@ -23,7 +26,7 @@ pub struct InitializationState<T, U> {
 }
 
 #[cfg(not(feature = "continuation"))]
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InitializationState<T, U> {
     pub eid: T,
     pub fid: T,
@@ -98,6 +101,139 @@ impl Default for InitializationState<u32, BigUint> {
     }
 }
 
+impl InitializationState<u32, BigUint> {
+    /// Whether it's the state before the first step of an execution. Eids and the host indices
+    /// start from 1, the entry function and the stack pointer are left to the caller.
+    pub fn is_genesis(&self, initial_memory_pages: u32, maximal_memory_pages: u32) -> bool {
+        let is_genesis = self.eid == 1
+            && self.iid == 0
+            && self.frame_id == 0
+            && self.host_public_inputs == 1
+            && self.context_in_index == 1
+            && self.context_out_index == 1
+            && self.external_host_call_call_index == 1
+            && self.initial_memory_pages == initial_memory_pages
+            && self.maximal_memory_pages == maximal_memory_pages;
+
+        #[cfg(feature = "continuation")]
+        let is_genesis = is_genesis && self.jops == BigUint::from(0u64);
+
+        is_genesis
+    }
+}
+
+/// Host public inputs consumed by the slice from `pre` to `post`.
+pub fn host_input_range(
+    pre: &InitializationState<u32, BigUint>,
+    post: &InitializationState<u32, BigUint>,
+) -> Range<u32> {
+    pre.host_public_inputs..post.host_public_inputs
+}
+
+/// Index ranges of the host inputs and the context consumed by a slice.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SliceIndexRanges {
+    pub host_public_inputs: Range<u32>,
+    pub context_in: Range<u32>,
+    pub context_out: Range<u32>,
+}
+
+/// Same as `host_input_range`, along with the context in/out index ranges.
+pub fn slice_index_ranges(
+    pre: &InitializationState<u32, BigUint>,
+    post: &InitializationState<u32, BigUint>,
+) -> SliceIndexRanges {
+    SliceIndexRanges {
+        host_public_inputs: host_input_range(pre, post),
+        context_in: pre.context_in_index..post.context_in_index,
+        context_out: pre.context_out_index..post.context_out_index,
+    }
+}
+
+/// Builds an `InitializationState<u32, BigUint>` with every field defaulting to zero.
+#[derive(Default)]
+pub struct InitializationStateBuilder {
+    state: InitializationState<u32, BigUint>,
+}
+
+impl InitializationStateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn eid(mut self, eid: u32) -> Self {
+        self.state.eid = eid;
+        self
+    }
+
+    pub fn fid(mut self, fid: u32) -> Self {
+        self.state.fid = fid;
+        self
+    }
+
+    pub fn iid(mut self, iid: u32) -> Self {
+        self.state.iid = iid;
+        self
+    }
+
+    pub fn frame_id(mut self, frame_id: u32) -> Self {
+        self.state.frame_id = frame_id;
+        self
+    }
+
+    pub fn sp(mut self, sp: u32) -> Self {
+        self.state.sp = sp;
+        self
+    }
+
+    pub fn host_public_inputs(mut self, host_public_inputs: u32) -> Self {
+        self.state.host_public_inputs = host_public_inputs;
+        self
+    }
+
+    pub fn context_in_index(mut self, context_in_index: u32) -> Self {
+        self.state.context_in_index = context_in_index;
+        self
+    }
+
+    pub fn context_out_index(mut self, context_out_index: u32) -> Self {
+        self.state.context_out_index = context_out_index;
+        self
+    }
+
+    pub fn external_host_call_call_index(mut self, external_host_call_call_index: u32) -> Self {
+        self.state.external_host_call_call_index = external_host_call_call_index;
+        self
+    }
+
+    pub fn initial_memory_pages(mut self, initial_memory_pages: u32) -> Self {
+        self.state.initial_memory_pages = initial_memory_pages;
+        self
+    }
+
+    pub fn maximal_memory_pages(mut self, maximal_memory_pages: u32) -> Self {
+        self.state.maximal_memory_pages = maximal_memory_pages;
+        self
+    }
+
+    /// Ignored without the `continuation` feature.
+    pub fn jops(self, _jops: BigUint) -> Self {
+        #[cfg(feature = "continuation")]
+        let state = InitializationState {
+            jops: _jops,
+            ..self.state
+        };
+        #[cfg(not(feature = "continuation"))]
+        let state = self.state;
+
+        Self { state }
+    }
+
+    pub fn build(self) -> InitializationState<u32, BigUint> {
+        self.state
+    }
+}
+
 impl<T: Clone> InitializationState<T, T> {
     pub fn plain(&self) -> Vec<T> {
         let mut v = vec![];
@@ -165,6 +301,13 @@ impl<T, U> InitializationState<T, U> {
     }
 }
 
+impl<F: FieldExt> InitializationState<AssignedCell<F, F>, AssignedCell<F, F>> {
+    // Read back the assigned values, None if assigned without witness.
+    pub fn values(&self) -> InitializationState<Option<F>, Option<F>> {
+        self.map(|cell| cell.value().cloned(), |cell| cell.value().cloned())
+    }
+}
+
 impl<T, U, E> InitializationState<Result<T, E>, Result<U, E>> {
     pub fn transpose(self) -> Result<InitializationState<T, U>, E> {
         Ok(InitializationState {
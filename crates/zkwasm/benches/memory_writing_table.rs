@@ -0,0 +1,92 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BatchSize;
+use criterion::Criterion;
+use delphinus_zkwasm::circuits::utils::table_entry::MemoryWritingTable;
+use specs::mtable::AccessType;
+use specs::mtable::LocationType;
+use specs::mtable::MTable;
+use specs::mtable::MemoryTableEntry;
+use specs::mtable::VarType;
+
+// A memory table grouped by location, each location is initialized then accessed at increasing
+// eids, about one access in three being a read.
+fn synthetic_mtable(locations: u32, max_accesses: u64) -> MTable {
+    let mut seed = 0x2545f491u64;
+    let mut next = |bound: u64| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+
+        (seed >> 33) % bound
+    };
+
+    let mut entries = vec![];
+    for ltype in [
+        LocationType::Stack,
+        LocationType::Heap,
+        LocationType::Global,
+    ] {
+        for offset in 0..locations {
+            let mut eid = 0;
+
+            entries.push(MemoryTableEntry {
+                eid,
+                offset,
+                ltype,
+                atype: AccessType::Init,
+                vtype: VarType::I64,
+                is_mutable: true,
+                value: next(100),
+            });
+
+            for _ in 0..next(max_accesses) {
+                eid += 1 + next(3) as u32;
+
+                entries.push(MemoryTableEntry {
+                    eid,
+                    offset,
+                    ltype,
+                    atype: if next(3) == 0 {
+                        AccessType::Read
+                    } else {
+                        AccessType::Write
+                    },
+                    vtype: VarType::I64,
+                    is_mutable: true,
+                    value: next(100),
+                });
+            }
+        }
+    }
+
+    MTable::from(entries)
+}
+
+fn bench_memory_writing_table(c: &mut Criterion) {
+    let mtable = synthetic_mtable(10000, 64);
+
+    let mut group = c.benchmark_group("memory_writing_table");
+    group.sample_size(10);
+
+    group.bench_function("parallel", |b| {
+        b.iter_batched(
+            || mtable.clone(),
+            |mtable| MemoryWritingTable::from_with_common_range(1 << 22, mtable),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("sequential", |b| {
+        b.iter_batched(
+            || mtable.clone(),
+            |mtable| MemoryWritingTable::from_with_common_range_sequential(1 << 22, mtable),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_memory_writing_table);
+criterion_main!(benches);
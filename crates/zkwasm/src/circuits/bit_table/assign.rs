@@ -6,7 +6,7 @@ use halo2_proofs::plonk::Error;
 use specs::itable::UnaryOp;
 use specs::step::StepInfo;
 
-use crate::circuits::utils::table_entry::EventTableWithMemoryInfo;
+use crate::circuits::utils::table_entry::EventTableEntries;
 use crate::circuits::utils::Context;
 
 use super::BitTableChip;
@@ -24,11 +24,10 @@ pub(crate) struct BitTableAssign {
     result: u64,
 }
 
-impl BitTableTrait for EventTableWithMemoryInfo {
+impl<T: EventTableEntries + ?Sized> BitTableTrait for T {
     fn filter_bit_table_entries(&self) -> Vec<BitTableAssign> {
-        self.0
-            .iter()
-            .filter_map(|entry| match &entry.eentry.step_info {
+        self.event_entries()
+            .filter_map(|eentry| match &eentry.step_info {
                 StepInfo::I32BinBitOp {
                     class,
                     left,
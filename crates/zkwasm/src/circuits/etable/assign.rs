@@ -2,28 +2,61 @@ use halo2_proofs::arithmetic::FieldExt;
 use halo2_proofs::circuit::AssignedCell;
 use halo2_proofs::circuit::Layouter;
 use halo2_proofs::circuit::Region;
+use halo2_proofs::plonk::Advice;
+use halo2_proofs::plonk::Column;
 use halo2_proofs::plonk::Error;
+use halo2_proofs::plonk::Fixed;
 use log::debug;
+use log::warn;
 use num_bigint::BigUint;
 use rayon::iter::IndexedParallelIterator;
+use rayon::iter::IntoParallelIterator;
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
 use specs::configure_table::ConfigureTable;
+use specs::etable::EventTableEntry;
 use specs::itable::InstructionTable;
 use specs::itable::OpcodeClassPlain;
+use specs::mtable::AccessType;
+use specs::state::slice_index_ranges;
 use specs::state::InitializationState;
+use specs::state::SliceIndexRanges;
 use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::ops::Deref;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use super::EventTableChip;
 use super::OpcodeConfig;
 use super::EVENT_TABLE_ENTRY_ROWS;
 use crate::circuits::cell::CellExpression;
 use crate::circuits::utils::bn_to_field;
+use crate::circuits::utils::checked_bn_to_field;
+use crate::circuits::utils::checked_u32_to_field;
+use crate::circuits::utils::interval_index::IntervalIndex;
+use crate::circuits::utils::step_status::status_to_init_state;
 use crate::circuits::utils::step_status::Status;
+use crate::circuits::utils::step_status::StatusRecord;
 use crate::circuits::utils::step_status::StepStatus;
+use crate::circuits::utils::table_entry::EventTableEntries;
+use crate::circuits::utils::table_entry::EventTableEntryWithMemoryInfo;
 use crate::circuits::utils::table_entry::EventTableWithMemoryInfo;
+use crate::circuits::utils::table_entry::MemoryWritingTable;
 use crate::circuits::utils::Context;
+use crate::error::ContextOrderError;
+use crate::error::EtableError;
+#[cfg(feature = "continuation")]
+use crate::error::JopsChainError;
+use crate::error::RangeError;
+use crate::error::RestMopsMismatch;
+use crate::error::TraceIssue;
+use crate::runtime::state::update_initialization_state_over;
 
 /*
  * Etable Layouter with Continuation
@@ -59,9 +92,11 @@ use crate::circuits::utils::Context;
  * |          |  1   |   0    |            |      |        |
  * | -------- | ---- | ------ | ---------- | ---- | ------ |
  * |          |  0   |   0    | constant 0 |      |        | permutation with post image table/jops constrain with jtable
+ *
+ * Both layouts are followed by `extra_reserved_rows` rows with sel = 0, if any.
  */
 #[derive(Debug)]
-pub(in crate::circuits) struct EventTablePermutationCells<F: FieldExt> {
+pub struct EventTablePermutationCells<F: FieldExt> {
     pub(in crate::circuits) rest_mops: AssignedCell<F, F>,
     // rest_jops cell at first step
     pub(in crate::circuits) rest_jops: Option<AssignedCell<F, F>>,
@@ -69,6 +104,211 @@ pub(in crate::circuits) struct EventTablePermutationCells<F: FieldExt> {
         InitializationState<AssignedCell<F, F>, AssignedCell<F, F>>,
     pub(in crate::circuits) post_initialization_state:
         InitializationState<AssignedCell<F, F>, AssignedCell<F, F>>,
+    // plain value of rest_mops
+    plain_rest_mops: u32,
+    // plain values of the boundary states
+    plain_states: (
+        InitializationState<u32, BigUint>,
+        InitializationState<u32, BigUint>,
+    ),
+}
+
+/// The initialization state at the beginning of a slice.
+#[derive(Clone, Copy, Debug)]
+pub struct PreState<'a>(pub &'a InitializationState<u32, BigUint>);
+
+/// The initialization state at the end of a slice.
+#[derive(Clone, Copy, Debug)]
+pub struct PostState<'a>(pub &'a InitializationState<u32, BigUint>);
+
+impl Deref for PreState<'_> {
+    type Target = InitializationState<u32, BigUint>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl Deref for PostState<'_> {
+    type Target = InitializationState<u32, BigUint>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+pub struct SliceInput<'a> {
+    pub itable: &'a InstructionTable,
+    pub event_table: &'a EventTableWithMemoryInfo,
+    pub configure_table: &'a ConfigureTable,
+    pub initialization_state: PreState<'a>,
+    pub post_initialization_state: PostState<'a>,
+    pub is_last_slice: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct AssignStats {
+    pub entries: usize,
+    pub capability: usize,
+    // rows taken by the entries, excluding padding
+    pub rows_used: usize,
+    // entries / capability, 0 if the capability is 0
+    pub fill_ratio: f64,
+}
+
+/// Per slice record of what verifying and reproducing its proof relies on, see
+/// `EventTableChip::proof_manifest`.
+#[derive(Clone, Debug, Serialize)]
+pub struct ProofManifest {
+    // `EventTableChip::slice_seed` of the pre and post states
+    pub pre_state_digest: [u8; 32],
+    pub post_state_digest: [u8; 32],
+    pub entries: usize,
+    pub rest_mops: u32,
+    // jops of the terminal status
+    pub terminal_jops: BigUint,
+    pub index_ranges: SliceIndexRanges,
+    // `MemoryWritingTable::commitment` with sha256
+    pub memory_commitment: [u8; 32],
+}
+
+/// Inputs of an opcode config assignment, passed to the hook installed by
+/// `EventTableChip::with_op_assign_hook`.
+#[derive(Clone, Debug)]
+pub struct OpAssignEvent {
+    pub class: OpcodeClassPlain,
+    pub eid: u32,
+    pub next_eid: u32,
+    pub sp: u32,
+    pub next_sp: u32,
+    pub rest_mops: u32,
+}
+
+/// Totals accumulated across many `assign` calls, e.g. for a live dashboard.
+#[derive(Debug, Default)]
+pub struct AssignmentCounters {
+    entries: AtomicUsize,
+    rows: AtomicUsize,
+    slices: AtomicUsize,
+}
+
+impl AssignmentCounters {
+    fn record(&self, entries: usize) {
+        self.entries.fetch_add(entries, Ordering::Relaxed);
+        self.rows
+            .fetch_add(entries * EVENT_TABLE_ENTRY_ROWS as usize, Ordering::Relaxed);
+        self.slices.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn entries(&self) -> usize {
+        self.entries.load(Ordering::Relaxed)
+    }
+
+    // rows taken by the entries, excluding padding
+    pub fn rows(&self) -> usize {
+        self.rows.load(Ordering::Relaxed)
+    }
+
+    pub fn slices(&self) -> usize {
+        self.slices.load(Ordering::Relaxed)
+    }
+}
+
+impl<F: FieldExt> EventTablePermutationCells<F> {
+    /// The rest_mops assigned at the first step.
+    pub fn rest_mops_value(&self) -> u32 {
+        self.plain_rest_mops
+    }
+
+    /// The boundary values exposed to the verifier, in the following order:
+    ///   1. pre_initialization_state, in `plain()` order (including jops if continuation),
+    ///   2. post_initialization_state, in `plain()` order,
+    ///   3. rest_mops,
+    ///   4. rest_jops, only if continuation is disabled.
+    ///
+    /// `None` if the cells are assigned without witness, e.g. for key generation.
+    pub fn public_inputs(&self) -> Option<Vec<F>> {
+        self.pre_initialization_state
+            .plain()
+            .iter()
+            .chain(self.post_initialization_state.plain().iter())
+            .chain(std::iter::once(&self.rest_mops))
+            .chain(self.rest_jops.iter())
+            .map(|cell| cell.value().copied())
+            .collect()
+    }
+
+    /// The (pre, post) initialization states as plain numbers, as passed to the assignment.
+    pub fn boundary_states(
+        &self,
+    ) -> (
+        &InitializationState<u32, BigUint>,
+        &InitializationState<u32, BigUint>,
+    ) {
+        (&self.plain_states.0, &self.plain_states.1)
+    }
+}
+
+/// Check the (sel, enable) flags of each row group against the layout documented above: the
+/// first `capability` groups have `sel = 1`, the first `entries` of them `enable = 1`, and the
+/// group of the post initialization state has both flags 0.
+pub fn verify_step_layout(
+    capability: usize,
+    entries: usize,
+    layout: &[(bool, bool)],
+) -> Result<(), EtableError> {
+    for group in 0..usize::max(layout.len(), capability + 1) {
+        let expected = (group < capability, group < entries && group < capability);
+        let actual = layout.get(group).cloned().unwrap_or((false, false));
+
+        if actual != expected {
+            return Err(EtableError::LayoutMismatch {
+                group,
+                expected,
+                actual,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Check the jops carried over a sequence of slices, `states` holds the (pre, post) states of
+/// each slice in order. Jops never decreases within a slice and each slice starts with the jops
+/// of the previous one.
+#[cfg(feature = "continuation")]
+pub fn validate_jops_chain(
+    states: &[InitializationState<u32, BigUint>],
+) -> Result<(), JopsChainError> {
+    if states.len() % 2 != 0 {
+        return Err(JopsChainError::UnpairedStates(states.len()));
+    }
+
+    for (slice, pair) in states.chunks(2).enumerate() {
+        let (pre, post) = (&pair[0], &pair[1]);
+
+        if post.jops < pre.jops {
+            return Err(JopsChainError::Decreasing {
+                slice,
+                pre: pre.jops.clone(),
+                post: post.jops.clone(),
+            });
+        }
+
+        if slice > 0 {
+            let outgoing = &states[slice * 2 - 1].jops;
+
+            if *outgoing != pre.jops {
+                return Err(JopsChainError::CarryOverMismatch {
+                    slice,
+                    outgoing: outgoing.clone(),
+                    incoming: pre.jops.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(())
 }
 
 impl<F: FieldExt> EventTableChip<F> {
@@ -76,66 +316,103 @@ impl<F: FieldExt> EventTableChip<F> {
         &self,
         ctx: &mut Context<'_, F>,
         state: &InitializationState<u32, BigUint>,
+    ) -> Result<InitializationState<AssignedCell<F, F>, AssignedCell<F, F>>, EtableError> {
+        Ok(self.assign_step_state_with_values(ctx, state, &self.step_state_values(state)?)?)
+    }
+
+    // Precompute the field elements of a state that is assigned repeatedly, e.g. for padding.
+    fn step_state_values(
+        &self,
+        state: &InitializationState<u32, BigUint>,
+    ) -> Result<InitializationState<F, F>, RangeError> {
+        state
+            .map(
+                |v| Ok(F::from(*v as u64)),
+                |v| checked_bn_to_field(v, &self.modulus),
+            )
+            .transpose()
+    }
+
+    // `values` must be `step_state_values(state)`, u32 state cells still need the plain value to
+    // decompose if continuation is enabled.
+    fn assign_step_state_with_values(
+        &self,
+        ctx: &mut Context<'_, F>,
+        state: &InitializationState<u32, BigUint>,
+        values: &InitializationState<F, F>,
     ) -> Result<InitializationState<AssignedCell<F, F>, AssignedCell<F, F>>, Error> {
+        let cells = self.assign_step_state_cells(ctx, state, values, false)?;
+
+        // Every field is assigned unless sparse.
+        Ok(cells.map(|cell| cell.clone().unwrap(), |cell| cell.clone().unwrap()))
+    }
+
+    // Same as `assign_step_state_with_values` but leaves the zero fields unassigned, which keeps
+    // the values since cells default to zero. No cell is returned, so it only fits padding.
+    fn assign_nonzero_step_state(
+        &self,
+        ctx: &mut Context<'_, F>,
+        state: &InitializationState<u32, BigUint>,
+        values: &InitializationState<F, F>,
+    ) -> Result<(), Error> {
+        self.assign_step_state_cells(ctx, state, values, true)
+            .map(|_| ())
+    }
+
+    // If `sparse`, the zero fields are skipped and have no cell.
+    fn assign_step_state_cells(
+        &self,
+        ctx: &mut Context<'_, F>,
+        _state: &InitializationState<u32, BigUint>,
+        values: &InitializationState<F, F>,
+        sparse: bool,
+    ) -> Result<InitializationState<Option<AssignedCell<F, F>>, Option<AssignedCell<F, F>>>, Error>
+    {
         cfg_if::cfg_if! {
             if #[cfg(feature="continuation")] {
                 macro_rules! assign_u32_state {
-                    ($cell:ident, $value:expr) => {
-                        self.config.common_config.$cell.assign(ctx, $value)?
+                    ($cell:ident, $field:ident) => {
+                        (!sparse || values.$field != F::zero())
+                            .then(|| self.config.common_config.$cell.assign(ctx, _state.$field))
+                            .transpose()?
                     };
                 }
             } else {
                 macro_rules! assign_u32_state {
-                    ($cell:ident, $value:expr) => {
-                        self.config.common_config.$cell.assign_u32(ctx, $value)?
+                    ($cell:ident, $field:ident) => {
+                        (!sparse || values.$field != F::zero())
+                            .then(|| self.config.common_config.$cell.assign(ctx, values.$field))
+                            .transpose()?
                     };
                 }
             }
         }
 
-        macro_rules! assign_common_range_advice {
-            ($cell:ident, $value:expr) => {
-                self.config
-                    .common_config
-                    .$cell
-                    .assign(ctx, F::from($value as u64))?
+        macro_rules! assign_advice {
+            ($cell:ident, $field:ident) => {
+                (!sparse || values.$field != F::zero())
+                    .then(|| self.config.common_config.$cell.assign(ctx, values.$field))
+                    .transpose()?
             };
         }
 
-        #[cfg(feature = "continuation")]
-        macro_rules! assign_biguint {
-            ($cell:ident, $value:expr) => {
-                self.config
-                    .common_config
-                    .$cell
-                    .assign(ctx, bn_to_field(&$value))?
-            };
-        }
+        let eid = assign_u32_state!(eid_cell, eid);
+        let fid = assign_advice!(fid_cell, fid);
+        let iid = assign_advice!(iid_cell, iid);
+        let sp = assign_advice!(sp_cell, sp);
+        let frame_id = assign_u32_state!(frame_id_cell, frame_id);
 
-        let eid = assign_u32_state!(eid_cell, state.eid);
-        let fid = assign_common_range_advice!(fid_cell, state.fid);
-        let iid = assign_common_range_advice!(iid_cell, state.iid);
-        let sp = assign_common_range_advice!(sp_cell, state.sp);
-        let frame_id = assign_u32_state!(frame_id_cell, state.frame_id);
-
-        let host_public_inputs =
-            assign_common_range_advice!(input_index_cell, state.host_public_inputs);
-        let context_in_index =
-            assign_common_range_advice!(context_input_index_cell, state.context_in_index);
-        let context_out_index =
-            assign_common_range_advice!(context_output_index_cell, state.context_out_index);
-        let external_host_call_call_index = assign_common_range_advice!(
-            external_host_call_index_cell,
-            state.external_host_call_call_index
-        );
+        let host_public_inputs = assign_advice!(input_index_cell, host_public_inputs);
+        let context_in_index = assign_advice!(context_input_index_cell, context_in_index);
+        let context_out_index = assign_advice!(context_output_index_cell, context_out_index);
+        let external_host_call_call_index =
+            assign_advice!(external_host_call_index_cell, external_host_call_call_index);
 
-        let initial_memory_pages =
-            assign_common_range_advice!(mpages_cell, state.initial_memory_pages);
-        let maximal_memory_pages =
-            assign_common_range_advice!(maximal_memory_pages_cell, state.maximal_memory_pages);
+        let initial_memory_pages = assign_advice!(mpages_cell, initial_memory_pages);
+        let maximal_memory_pages = assign_advice!(maximal_memory_pages_cell, maximal_memory_pages);
 
         #[cfg(feature = "continuation")]
-        let jops = assign_biguint!(jops_cell, state.jops);
+        let jops = assign_advice!(jops_cell, jops);
 
         ctx.step(EVENT_TABLE_ENTRY_ROWS as usize);
 
@@ -166,18 +443,18 @@ impl<F: FieldExt> EventTableChip<F> {
         &self,
         op_configs: Arc<BTreeMap<OpcodeClassPlain, OpcodeConfig<F>>>,
         itable: &InstructionTable,
-        event_table: &EventTableWithMemoryInfo,
-        _initialization_state: &InitializationState<u32, BigUint>,
+        event_table: &dyn EventTableEntries,
+        _initialization_state: PreState<'_>,
     ) -> (u32, BigUint) {
-        let (rest_mops, _rest_jops) = event_table.0.iter().fold(
+        let (rest_mops, _rest_jops) = event_table.event_entries().fold(
             (0, BigUint::from(0u64)),
-            |(rest_mops_sum, rest_jops_sum), entry| {
-                let instruction = entry.eentry.get_instruction(itable);
+            |(rest_mops_sum, rest_jops_sum), eentry| {
+                let instruction = eentry.get_instruction(itable);
 
                 let op_config = op_configs.get(&((&instruction.opcode).into())).unwrap();
 
                 (
-                    rest_mops_sum + op_config.0.memory_writing_ops(&entry.eentry),
+                    rest_mops_sum + op_config.0.memory_writing_ops(eentry),
                     rest_jops_sum + op_config.0.jops(),
                 )
             },
@@ -192,343 +469,1480 @@ impl<F: FieldExt> EventTableChip<F> {
         }
     }
 
-    fn init(&self, ctx: &mut Context<'_, F>) -> Result<(), Error> {
-        for _ in 0..self.capability {
-            ctx.region.assign_fixed(
-                || "etable: step sel",
-                self.config.step_sel,
-                ctx.offset,
-                || Ok(F::one()),
-            )?;
-
-            ctx.step(EVENT_TABLE_ENTRY_ROWS as usize);
-        }
-
-        ctx.region.assign_advice_from_constant(
-            || "etable: rest mops terminates",
-            self.config.common_config.rest_mops_cell.cell.col,
-            ctx.offset,
-            F::zero(),
-        )?;
-
-        #[cfg(not(feature = "continuation"))]
-        ctx.region.assign_advice_from_constant(
-            || "etable: rest jops terminates",
-            self.config.common_config.jops_cell.cell.col,
-            ctx.offset,
-            F::zero(),
-        )?;
-
-        Ok(())
+    fn op_config_of(
+        &self,
+        itable: &InstructionTable,
+        eentry: &EventTableEntry,
+    ) -> &OpcodeConfig<F> {
+        self.config
+            .op_configs
+            .get(&((&eentry.get_instruction(itable).opcode).into()))
+            .unwrap()
     }
 
-    // Get the cell to permutation, the meaningless value should be overwritten.
-    fn assign_rest_ops_first_step(
+    // The rest_mops folded from the op configs along with the (eid, memory writes) of each entry,
+    // which the rest_mops checks compare it against.
+    fn rest_mops_and_writes<'a>(
         &self,
-        ctx: &mut Context<'_, F>,
-    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), Error> {
-        let rest_mops_cell = self
-            .config
-            .common_config
-            .rest_mops_cell
-            .assign(ctx, F::zero())?;
+        itable: &InstructionTable,
+        event_table: &'a EventTableWithMemoryInfo,
+        initialization_state: PreState<'_>,
+    ) -> (u32, impl Iterator<Item = (u32, u32)> + 'a) {
+        let (rest_mops, _) = self.compute_rest_mops_and_jops(
+            self.config.op_configs.clone(),
+            itable,
+            event_table,
+            initialization_state,
+        );
 
-        let rest_jops_cell = self.config.common_config.jops_cell.assign(ctx, F::zero())?;
+        let writes = event_table.0.iter().map(|entry| {
+            let writes = entry
+                .memory_rw_entires
+                .iter()
+                .filter(|rw| rw.entry.atype == AccessType::Write)
+                .count() as u32;
 
-        Ok((rest_mops_cell, rest_jops_cell))
+            (entry.eentry.eid, writes)
+        });
+
+        (rest_mops, writes)
     }
 
-    fn assign_padding_and_post_initialization_state(
+    /// Check that rest_mops assigned to each step never goes below zero and terminates at zero,
+    /// decrementing the total folded from the op configs by the memory writes each entry actually
+    /// performs.
+    pub fn validate_rest_mops_sequence(
         &self,
-        ctx: &mut Context<'_, F>,
-        initialization_state: &InitializationState<u32, BigUint>,
-    ) -> Result<InitializationState<AssignedCell<F, F>, AssignedCell<F, F>>, Error> {
-        while ctx.offset < self.capability * EVENT_TABLE_ENTRY_ROWS as usize {
-            self.assign_step_state(ctx, initialization_state)?;
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        initialization_state: PreState<'_>,
+    ) -> Result<(), EtableError> {
+        let (mut rest_mops, writes) =
+            self.rest_mops_and_writes(itable, event_table, initialization_state);
+
+        for (eid, writes) in writes {
+            rest_mops = rest_mops
+                .checked_sub(writes)
+                .ok_or(EtableError::RestMopsUnderflow { eid })?;
         }
 
-        self.assign_step_state(ctx, initialization_state)
+        if rest_mops != 0 {
+            return Err(EtableError::RestMopsNotTerminated { rest_mops });
+        }
+
+        Ok(())
     }
 
-    fn assign_entries(
+    /// Check that jops decremented by each step never goes below zero and terminates at zero when
+    /// starting from `rest_jops`, the rest jops of the frame table.
+    #[cfg(not(feature = "continuation"))]
+    pub fn validate_rest_jops_sequence(
         &self,
-        region: &Region<'_, F>,
-        op_configs: Arc<BTreeMap<OpcodeClassPlain, OpcodeConfig<F>>>,
         itable: &InstructionTable,
-        event_table: &EventTableWithMemoryInfo,
-        configure_table: &ConfigureTable,
-        initialization_state: &InitializationState<u32, BigUint>,
-        post_initialization_state: &InitializationState<u32, BigUint>,
-        rest_mops: u32,
-        jops: BigUint,
-    ) -> Result<(), Error> {
-        macro_rules! assign_advice {
-            ($ctx:expr, $cell:ident, $value:expr) => {
-                self.config
-                    .common_config
-                    .$cell
-                    .assign($ctx, $value)
-                    .unwrap()
-            };
-        }
+        event_table: &dyn EventTableEntries,
+        rest_jops: &BigUint,
+    ) -> Result<(), EtableError> {
+        let mut jops = rest_jops.clone();
 
-        macro_rules! assign_advice_cell {
-            ($ctx:expr, $cell:ident, $value:expr) => {
-                $cell.assign($ctx, $value).unwrap()
-            };
+        for eentry in event_table.event_entries() {
+            Self::decrement_jops(&mut jops, self.op_config_of(itable, eentry), eentry)?;
         }
 
-        /*
-         * The length of event_table equals 0: without_witness
-         */
-        if event_table.0.len() == 0 {
-            return Ok(());
+        if jops != BigUint::from(0u64) {
+            return Err(EtableError::JopsNotTerminated { jops });
         }
 
-        let status = {
-            let mut host_public_inputs = initialization_state.host_public_inputs;
-            let mut context_in_index = initialization_state.context_in_index;
-            let mut context_out_index = initialization_state.context_out_index;
-            let mut external_host_call_call_index =
-                initialization_state.external_host_call_call_index;
+        Ok(())
+    }
 
-            let mut rest_mops = rest_mops;
-            let mut jops = jops;
+    /// Cross-check rest_mops: the total folded from the op configs must equal the number of
+    /// memory writes of the entries.
+    pub fn verify_rest_mops_consistency(
+        &self,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        initialization_state: PreState<'_>,
+    ) -> Result<(), RestMopsMismatch> {
+        let (folded, writes) = self.rest_mops_and_writes(itable, event_table, initialization_state);
 
-            let mut status = event_table
-                .0
-                .iter()
-                .map(|entry| {
-                    let op_config = op_configs
-                        .get(&((&entry.eentry.get_instruction(itable).opcode).into()))
-                        .unwrap();
-
-                    let status = Status {
-                        eid: entry.eentry.eid,
-                        fid: entry.eentry.fid,
-                        iid: entry.eentry.iid,
-                        sp: entry.eentry.sp,
-                        last_jump_eid: entry.eentry.last_jump_eid,
-                        allocated_memory_pages: entry.eentry.allocated_memory_pages,
-
-                        rest_mops,
-                        jops: jops.clone(),
-
-                        host_public_inputs,
-                        context_in_index,
-                        context_out_index,
-                        external_host_call_call_index,
-
-                        itable,
-                    };
+        let recomputed = writes.map(|(_, writes)| writes).sum();
 
-                    if op_config.0.is_host_public_input(&entry.eentry) {
-                        host_public_inputs += 1;
-                    }
-                    if op_config.0.is_context_input_op(&entry.eentry) {
-                        context_in_index += 1;
-                    }
-                    if op_config.0.is_context_output_op(&entry.eentry) {
-                        context_out_index += 1;
-                    }
-                    if op_config.0.is_external_host_call(&entry.eentry) {
-                        external_host_call_call_index += 1;
-                    }
-
-                    rest_mops -= op_config.0.memory_writing_ops(&entry.eentry);
-                    if cfg!(feature = "continuation") {
-                        jops += op_config.0.jops()
-                    } else {
-                        jops -= op_config.0.jops()
-                    }
+        if folded != recomputed {
+            return Err(RestMopsMismatch { folded, recomputed });
+        }
 
-                    status
-                })
-                .collect::<Vec<_>>();
+        Ok(())
+    }
 
-            assert_eq!(
-                post_initialization_state.host_public_inputs,
-                host_public_inputs
-            );
-            assert_eq!(post_initialization_state.context_in_index, context_in_index);
-            assert_eq!(
-                post_initialization_state.context_out_index,
-                context_out_index
-            );
-            assert_eq!(
-                post_initialization_state.external_host_call_call_index,
-                external_host_call_call_index
+    /// Run the precondition checks of proving at once: capability, eid contiguity, memory pages,
+    /// read coverage and the rest_mops sequence. All issues found are reported.
+    pub fn validate_trace(
+        &self,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        mtable: &MemoryWritingTable,
+        configure_table: &ConfigureTable,
+        initialization_state: PreState<'_>,
+    ) -> Result<(), Vec<TraceIssue>> {
+        let mut issues = vec![];
+
+        if !self.fits(event_table) {
+            issues.push(
+                EtableError::CapabilityExceeded {
+                    entries: event_table.0.len(),
+                    capability: self.capability,
+                }
+                .into(),
             );
+        }
 
-            let terminate_status = Status {
-                eid: post_initialization_state.eid,
-                fid: post_initialization_state.fid,
-                iid: post_initialization_state.iid,
-                sp: post_initialization_state.sp,
-                last_jump_eid: post_initialization_state.frame_id,
-                allocated_memory_pages: post_initialization_state.initial_memory_pages,
+        let mut expected_eid = initialization_state.eid;
+        let interval_index = IntervalIndex::new(mtable);
 
-                host_public_inputs: post_initialization_state.host_public_inputs,
-                context_in_index: post_initialization_state.context_in_index,
-                context_out_index: post_initialization_state.context_out_index,
-                external_host_call_call_index: post_initialization_state
-                    .external_host_call_call_index,
+        for (index, entry) in event_table.0.iter().enumerate() {
+            let eentry = &entry.eentry;
 
-                rest_mops,
-                jops,
+            if eentry.eid != expected_eid {
+                issues.push(TraceIssue::NonContiguousEid {
+                    index,
+                    expected: expected_eid,
+                    actual: eentry.eid,
+                });
+            }
+            expected_eid = eentry.eid + 1;
 
-                itable,
-            };
+            for rw in entry.memory_effects() {
+                if rw.entry.atype == AccessType::Read
+                    && interval_index
+                        .query_point(rw.entry.ltype, rw.entry.offset, eentry.eid)
+                        .is_none()
+                {
+                    issues.push(TraceIssue::UncoveredRead {
+                        eid: eentry.eid,
+                        ltype: rw.entry.ltype,
+                        offset: rw.entry.offset,
+                    });
+                }
+            }
+        }
 
-            status.push(terminate_status);
+        if let Err(err) = Self::validate_memory_pages(event_table, configure_table) {
+            issues.push(err.into());
+        }
 
-            status
-        };
+        if let Err(err) =
+            self.validate_rest_mops_sequence(itable, event_table, initialization_state)
+        {
+            issues.push(err.into());
+        }
 
-        event_table
-            .0
-            .par_iter()
-            .enumerate()
-            .for_each(|(index, entry)| {
-                let mut ctx = Context::new(region);
-                ctx.step((EVENT_TABLE_ENTRY_ROWS as usize * index) as usize);
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
 
-                let instruction = entry.eentry.get_instruction(itable);
+    // Memory growing never exceeds the maximal memory pages, report the first violation.
+    fn validate_memory_pages(
+        event_table: &dyn EventTableEntries,
+        configure_table: &ConfigureTable,
+    ) -> Result<(), EtableError> {
+        match event_table
+            .event_entries()
+            .find(|eentry| eentry.allocated_memory_pages > configure_table.maximal_memory_pages)
+        {
+            Some(eentry) => Err(EtableError::MemoryPagesExceeded {
+                eid: eentry.eid,
+                allocated: eentry.allocated_memory_pages,
+                maximal: configure_table.maximal_memory_pages,
+            }),
+            None => Ok(()),
+        }
+    }
 
-                let step_status = StepStatus {
-                    current: &status[index],
-                    next: &status[index + 1],
-                    configure_table,
-                };
+    // Advance the host/context/external call counters (and jops if continuation) over one entry.
+    fn advance_counters(
+        op_config: &OpcodeConfig<F>,
+        eentry: &EventTableEntry,
+        state: &mut InitializationState<u32, BigUint>,
+    ) {
+        if op_config.0.is_host_public_input(eentry) {
+            state.host_public_inputs += 1;
+        }
+        if op_config.0.is_context_input_op(eentry) {
+            state.context_in_index += 1;
+        }
+        if op_config.0.is_context_output_op(eentry) {
+            state.context_out_index += 1;
+        }
+        if op_config.0.is_external_host_call(eentry) {
+            state.external_host_call_call_index += 1;
+        }
 
-                {
-                    let class: OpcodeClassPlain = (&instruction.opcode).into();
+        #[cfg(feature = "continuation")]
+        {
+            state.jops += op_config.0.jops();
+        }
+    }
 
-                    let op = self.config.common_config.ops[class.index()];
-                    assign_advice_cell!(&mut ctx, op, F::one());
-                }
+    /// Replay the context indices over the event table and check `rule(context_in_index,
+    /// context_out_index)` after each step, e.g. `|input, output| input <= output`.
+    pub fn validate_context_ordering(
+        &self,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        initialization_state: PreState<'_>,
+        rule: impl Fn(u32, u32) -> bool,
+    ) -> Result<(), ContextOrderError> {
+        let mut state = initialization_state.0.clone();
+
+        for entry in event_table.0.iter() {
+            let op_config = self.op_config_of(itable, &entry.eentry);
+
+            Self::advance_counters(op_config, &entry.eentry, &mut state);
+
+            if !rule(state.context_in_index, state.context_out_index) {
+                return Err(ContextOrderError {
+                    eid: entry.eentry.eid,
+                    context_in_index: state.context_in_index,
+                    context_out_index: state.context_out_index,
+                });
+            }
+        }
 
-                assign_advice!(&mut ctx, enabled_cell, F::one());
-                assign_advice!(
-                    &mut ctx,
-                    rest_mops_cell,
-                    F::from(status[index].rest_mops as u64)
-                );
-                assign_advice!(
-                    &mut ctx,
-                    itable_lookup_cell,
-                    bn_to_field(&instruction.encode)
-                );
-                assign_advice!(&mut ctx, jops_cell, bn_to_field(&status[index].jops));
+        Ok(())
+    }
 
-                {
-                    let op_config = op_configs.get(&((&instruction.opcode).into())).unwrap();
-                    op_config.0.assign(&mut ctx, &step_status, &entry).unwrap();
-                }
+    /// The number of host public inputs consumed by the event table, i.e. the increase of
+    /// `host_public_inputs` from the pre state to the post state.
+    pub fn host_input_count(
+        &self,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+    ) -> u32 {
+        event_table
+            .0
+            .iter()
+            .filter(|entry| {
+                self.op_config_of(itable, &entry.eentry)
+                    .0
+                    .is_host_public_input(&entry.eentry)
+            })
+            .count() as u32
+    }
 
-                // Be careful, the function will step context.
-                self.assign_step_state(
-                    &mut ctx,
-                    &InitializationState {
-                        eid: entry.eentry.eid,
-                        fid: entry.eentry.fid,
-                        iid: entry.eentry.iid,
-                        sp: entry.eentry.sp,
-                        frame_id: entry.eentry.last_jump_eid,
-
-                        host_public_inputs: status[index].host_public_inputs,
-                        context_in_index: status[index].context_in_index,
-                        context_out_index: status[index].context_out_index,
-                        external_host_call_call_index: status[index].external_host_call_call_index,
-
-                        initial_memory_pages: entry.eentry.allocated_memory_pages,
-                        maximal_memory_pages: configure_table.maximal_memory_pages,
-
-                        #[cfg(feature = "continuation")]
-                        jops: status[index].jops.clone(),
-
-                        #[cfg(not(feature = "continuation"))]
-                        _phantom: core::marker::PhantomData,
-                    },
-                )
-                .unwrap();
-            });
+    /// `(eid, external_host_call_call_index)` of each external host call, replaying the index
+    /// from the pre state as `assign_entries` does. The index is the one of the step, i.e. before
+    /// the call advances it.
+    pub fn host_call_trajectory(
+        &self,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        initialization_state: PreState<'_>,
+    ) -> Vec<(u32, u32)> {
+        let mut index = initialization_state.external_host_call_call_index;
 
-        Ok(())
+        event_table
+            .0
+            .iter()
+            .filter(|entry| {
+                self.op_config_of(itable, &entry.eentry)
+                    .0
+                    .is_external_host_call(&entry.eentry)
+            })
+            .map(|entry| {
+                index += 1;
+
+                (entry.eentry.eid, index - 1)
+            })
+            .collect()
     }
 
-    pub(in crate::circuits) fn assign(
+    /// Derive the post initialization state from the pre state by replaying the counters over the
+    /// event table, see `update_initialization_state`. `next_event_entry` is None for the last
+    /// slice.
+    pub fn derive_post_state(
         &self,
-        layouter: impl Layouter<F>,
-        itable: &InstructionTable,
         event_table: &EventTableWithMemoryInfo,
         configure_table: &ConfigureTable,
-        initialization_state: &InitializationState<u32, BigUint>,
-        post_initialization_state: &InitializationState<u32, BigUint>,
-        _is_last_slice: bool,
-    ) -> Result<EventTablePermutationCells<F>, Error> {
-        layouter.assign_region(
-            || "event table",
-            |region| {
-                let mut ctx = Context::new(region);
+        initialization_state: PreState<'_>,
+        next_event_entry: Option<&EventTableEntry>,
+    ) -> InitializationState<u32, BigUint> {
+        update_initialization_state_over(
+            initialization_state.0,
+            event_table.0.iter().map(|entry| &entry.eentry),
+            configure_table,
+            next_event_entry,
+        )
+    }
 
-                debug!("size of execution table: {}", event_table.0.len());
+    /// The pre state of a continuation slice starting at `boundary_eid` of `full_trace`, which
+    /// starts from `genesis`. Same as `derive_post_state` of the slice ending before
+    /// `boundary_eid`, i.e. the boundary may be any eid of the trace or past its end.
+    pub fn state_at_boundary(
+        &self,
+        full_trace: &EventTableWithMemoryInfo,
+        configure_table: &ConfigureTable,
+        genesis: &InitializationState<u32, BigUint>,
+        boundary_eid: u32,
+    ) -> InitializationState<u32, BigUint> {
+        let split = full_trace
+            .0
+            .partition_point(|entry| entry.eentry.eid < boundary_eid);
 
-                assert!(event_table.0.len() <= self.capability);
+        update_initialization_state_over(
+            genesis,
+            full_trace.0[..split].iter().map(|entry| &entry.eentry),
+            configure_table,
+            full_trace.0.get(split).map(|entry| &entry.eentry),
+        )
+    }
 
-                self.init(&mut ctx)?;
-                ctx.reset();
+    // Same as `assign_fixed` at the current offset, reporting the offset on failure.
+    fn assign_fixed_at(
+        &self,
+        ctx: &Context<'_, F>,
+        label: &'static str,
+        column: Column<Fixed>,
+        value: F,
+    ) -> Result<(), EtableError> {
+        ctx.region
+            .assign_fixed(|| label, column, ctx.offset, || Ok(value))
+            .map(|_| ())
+            .map_err(|source| self.region_error(ctx, label, source))
+    }
 
-                let pre_initialization_state =
-                    self.assign_step_state(&mut ctx, initialization_state)?;
-                ctx.reset();
+    // Same as `assign_advice_from_constant` at the current offset, reporting the offset on
+    // failure.
+    fn assign_constant_at(
+        &self,
+        ctx: &Context<'_, F>,
+        label: &'static str,
+        column: Column<Advice>,
+        value: F,
+    ) -> Result<(), EtableError> {
+        ctx.region
+            .assign_advice_from_constant(|| label, column, ctx.offset, value)
+            .map(|_| ())
+            .map_err(|source| self.region_error(ctx, label, source))
+    }
 
-                let (rest_mops_cell, _jops_cell) = self.assign_rest_ops_first_step(&mut ctx)?;
+    fn region_error(
+        &self,
+        ctx: &Context<'_, F>,
+        label: &'static str,
+        source: Error,
+    ) -> EtableError {
+        EtableError::RegionAssignment {
+            label,
+            offset: ctx.offset,
+            capability: self.capability,
+            source,
+        }
+    }
 
-                let (rest_mops, jops) = self.compute_rest_mops_and_jops(
-                    self.config.op_configs.clone(),
+    fn init(&self, ctx: &mut Context<'_, F>) -> Result<(), EtableError> {
+        for _ in 0..self.capability {
+            self.assign_fixed_at(ctx, "etable: step sel", self.config.step_sel, F::one())?;
+
+            ctx.step(EVENT_TABLE_ENTRY_ROWS as usize);
+        }
+
+        self.assign_constant_at(
+            ctx,
+            "etable: rest mops terminates",
+            self.config.common_config.rest_mops_cell.cell.col,
+            F::zero(),
+        )?;
+
+        #[cfg(not(feature = "continuation"))]
+        self.assign_constant_at(
+            ctx,
+            "etable: rest jops terminates",
+            self.config.common_config.jops_cell.cell.col,
+            F::zero(),
+        )?;
+
+        ctx.step(Self::reserved_rows());
+
+        for _ in 0..self.extra_reserved_rows {
+            self.assign_fixed_at(
+                ctx,
+                "etable: extra reserved rows",
+                self.config.step_sel,
+                F::zero(),
+            )?;
+
+            ctx.next();
+        }
+
+        Ok(())
+    }
+
+    // Get the cell to permutation, the meaningless value should be overwritten.
+    fn assign_rest_ops_first_step(
+        &self,
+        ctx: &mut Context<'_, F>,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), Error> {
+        let rest_mops_cell = self
+            .config
+            .common_config
+            .rest_mops_cell
+            .assign(ctx, F::zero())?;
+
+        let rest_jops_cell = self.config.common_config.jops_cell.assign(ctx, F::zero())?;
+
+        Ok((rest_mops_cell, rest_jops_cell))
+    }
+
+    fn assign_padding_and_post_initialization_state(
+        &self,
+        ctx: &mut Context<'_, F>,
+        base_offset: usize,
+        post_initialization_state: PostState<'_>,
+    ) -> Result<InitializationState<AssignedCell<F, F>, AssignedCell<F, F>>, EtableError> {
+        let padding_end = base_offset + self.capability * EVENT_TABLE_ENTRY_ROWS as usize;
+
+        // Otherwise the post initialization state would be assigned at an unexpected offset.
+        if ctx.offset > padding_end {
+            return Err(EtableError::PaddingOverrun {
+                offset: ctx.offset,
+                padding_end,
+            });
+        }
+
+        let values = self.step_state_values(&post_initialization_state)?;
+
+        while ctx.offset < padding_end {
+            if self.sparse_padding {
+                self.assign_nonzero_step_state(ctx, &post_initialization_state, &values)?;
+            } else {
+                self.assign_step_state_with_values(ctx, &post_initialization_state, &values)?;
+            }
+        }
+
+        Ok(self.assign_step_state_with_values(ctx, &post_initialization_state, &values)?)
+    }
+
+    /// The status of each step followed by the terminal status, as used to assign the event table.
+    pub fn status_sequence<'a>(
+        &self,
+        itable: &'a InstructionTable,
+        event_table: &dyn EventTableEntries,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+    ) -> Result<Vec<Status<'a>>, EtableError> {
+        let (rest_mops, jops) = self.compute_rest_mops_and_jops(
+            self.config.op_configs.clone(),
+            itable,
+            event_table,
+            initialization_state,
+        );
+
+        self.compute_status(
+            itable,
+            event_table,
+            initialization_state,
+            post_initialization_state,
+            rest_mops,
+            jops,
+        )
+    }
+
+    /// Render the values assigned for each step, one line per step followed by the terminal
+    /// status, as laid out in the diagram above.
+    pub fn render_assignment_table(
+        &self,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+    ) -> Result<String, EtableError> {
+        let status = self.status_sequence(
+            itable,
+            event_table,
+            initialization_state,
+            post_initialization_state,
+        )?;
+
+        let row = |eid: &dyn Display,
+                   enable: &dyn Display,
+                   rest_mops: &dyn Display,
+                   jops: &dyn Display,
+                   sp: &dyn Display,
+                   opcode: &dyn Display| {
+            format!(
+                "{:>10} | {:>6} | {:>10} | {:>20} | {:>10} | {}",
+                eid, enable, rest_mops, jops, sp, opcode
+            )
+        };
+
+        let mut lines = vec![row(
+            &"eid",
+            &"enable",
+            &"rest_mops",
+            &"jops",
+            &"sp",
+            &"opcode",
+        )];
+
+        for (entry, status) in event_table.0.iter().zip(status.iter()) {
+            let instruction = entry.eentry.get_instruction(itable);
+
+            lines.push(row(
+                &status.eid,
+                &1,
+                &status.rest_mops,
+                &status.jops,
+                &status.sp,
+                &format!("{:?}", instruction.opcode),
+            ));
+        }
+
+        if let Some(terminal) = status.last() {
+            lines.push(row(
+                &terminal.eid,
+                &0,
+                &terminal.rest_mops,
+                &terminal.jops,
+                &terminal.sp,
+                &"-",
+            ));
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    // Exposed to the tests for starting from an arbitrary jops total.
+    pub(crate) fn compute_status<'a>(
+        &self,
+        itable: &'a InstructionTable,
+        event_table: &dyn EventTableEntries,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+        rest_mops: u32,
+        jops: BigUint,
+    ) -> Result<Vec<Status<'a>>, EtableError> {
+        let mut counters = initialization_state.0.clone();
+
+        let mut rest_mops = rest_mops;
+        let mut jops = jops;
+
+        // One status per entry followed by the terminal one.
+        let mut status = Vec::with_capacity(event_table.len() + 1);
+
+        for eentry in event_table.event_entries() {
+            let op_config = self.op_config_of(itable, eentry);
+
+            status.push(Status {
+                eid: eentry.eid,
+                fid: eentry.fid,
+                iid: eentry.iid,
+                sp: eentry.sp,
+                last_jump_eid: eentry.last_jump_eid,
+                allocated_memory_pages: eentry.allocated_memory_pages,
+
+                rest_mops,
+                jops: jops.clone(),
+
+                host_public_inputs: counters.host_public_inputs,
+                context_in_index: counters.context_in_index,
+                context_out_index: counters.context_out_index,
+                external_host_call_call_index: counters.external_host_call_call_index,
+
+                itable,
+            });
+
+            Self::advance_counters(op_config, eentry, &mut counters);
+
+            rest_mops -= op_config.0.memory_writing_ops(eentry);
+            if cfg!(feature = "continuation") {
+                jops += op_config.0.jops()
+            } else {
+                Self::decrement_jops(&mut jops, op_config, eentry)?;
+            }
+        }
+
+        assert_eq!(
+            post_initialization_state.host_public_inputs,
+            counters.host_public_inputs
+        );
+        assert_eq!(
+            post_initialization_state.context_in_index,
+            counters.context_in_index
+        );
+        assert_eq!(
+            post_initialization_state.context_out_index,
+            counters.context_out_index
+        );
+        assert_eq!(
+            post_initialization_state.external_host_call_call_index,
+            counters.external_host_call_call_index
+        );
+
+        let terminate_status =
+            Self::terminate_status(itable, post_initialization_state, rest_mops, jops);
+
+        // The terminal push never reallocates.
+        debug_assert!(status.len() < status.capacity());
+        status.push(terminate_status);
+
+        Ok(status)
+    }
+
+    // BigUint panics on underflow, report the step instead.
+    fn decrement_jops(
+        jops: &mut BigUint,
+        op_config: &OpcodeConfig<F>,
+        eentry: &EventTableEntry,
+    ) -> Result<(), EtableError> {
+        let step_jops = op_config.0.jops();
+
+        if *jops < step_jops {
+            return Err(EtableError::JopsUnderflow { eid: eentry.eid });
+        }
+
+        *jops -= step_jops;
+
+        Ok(())
+    }
+
+    fn terminate_status<'a>(
+        itable: &'a InstructionTable,
+        post_initialization_state: PostState<'_>,
+        rest_mops: u32,
+        jops: BigUint,
+    ) -> Status<'a> {
+        // `frame_id` is the `last_jump_eid` of the next step, see `status_to_init_state`.
+        Status {
+            eid: post_initialization_state.eid,
+            fid: post_initialization_state.fid,
+            iid: post_initialization_state.iid,
+            sp: post_initialization_state.sp,
+            last_jump_eid: post_initialization_state.frame_id,
+            allocated_memory_pages: post_initialization_state.initial_memory_pages,
+
+            host_public_inputs: post_initialization_state.host_public_inputs,
+            context_in_index: post_initialization_state.context_in_index,
+            context_out_index: post_initialization_state.context_out_index,
+            external_host_call_call_index: post_initialization_state.external_host_call_call_index,
+
+            rest_mops,
+            jops,
+
+            itable,
+        }
+    }
+
+    /// The final state enforced by the event table, i.e. the last one of `status_sequence`. It's
+    /// serializable so the halt state can be published for verifiers.
+    pub fn compute_terminal_status<'a>(
+        &self,
+        itable: &'a InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+    ) -> Result<Status<'a>, EtableError> {
+        let mut status = self.status_sequence(
+            itable,
+            event_table,
+            initialization_state,
+            post_initialization_state,
+        )?;
+
+        // The sequence always ends with the terminal status.
+        Ok(status.pop().unwrap())
+    }
+
+    // Offset of the entry at `index`, reject overflow rather than wrapping to wrong rows.
+    fn entry_offset(base_offset: usize, index: usize) -> Result<usize, EtableError> {
+        (EVENT_TABLE_ENTRY_ROWS as usize)
+            .checked_mul(index)
+            .and_then(|offset| offset.checked_add(base_offset))
+            .ok_or(EtableError::OffsetOverflow { index })
+    }
+
+    fn assign_entries(
+        &self,
+        region: &Region<'_, F>,
+        base_offset: usize,
+        itable: &InstructionTable,
+        event_table: &dyn EventTableEntries,
+        configure_table: &ConfigureTable,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+        rest_mops: u32,
+        jops: BigUint,
+        status: Option<&[Status]>,
+    ) -> Result<(), EtableError> {
+        /*
+         * The length of event_table equals 0: without_witness, see `EventTableWithMemoryInfo::empty`
+         */
+        if event_table.is_empty() {
+            return Ok(());
+        }
+
+        let computed;
+        let status = match status {
+            Some(status) => status,
+            None => {
+                computed = self.compute_status(
                     itable,
                     event_table,
                     initialization_state,
-                );
+                    post_initialization_state,
+                    rest_mops,
+                    jops,
+                )?;
+
+                &computed
+            }
+        };
 
-                self.assign_entries(
+        // jops is monotonic along the statuses, neither end should wrap when converted to field.
+        for current_status in [status.first(), status.last()].into_iter().flatten() {
+            if current_status.jops >= self.modulus {
+                return Err(EtableError::JopsOverflow {
+                    jops: current_status.jops.clone(),
+                });
+            }
+        }
+
+        if let Some(max_jops_bits) = self.max_jops_bits {
+            for current_status in status {
+                let bits = current_status.jops.bits();
+
+                if bits > max_jops_bits {
+                    return Err(EtableError::JopsTooLarge {
+                        eid: current_status.eid,
+                        bits,
+                    });
+                }
+            }
+        }
+
+        self.assign_entries_with_statuses(
+            region,
+            base_offset,
+            itable,
+            event_table,
+            configure_table,
+            status,
+            None,
+        )
+    }
+
+    // Assign the step of `entry` at the offset of `ctx`, stepping it past the entry.
+    fn assign_entry_at(
+        &self,
+        ctx: &mut Context<'_, F>,
+        itable: &InstructionTable,
+        entry: &EventTableEntryWithMemoryInfo,
+        current_status: &Status,
+        next_status: &Status,
+        configure_table: &ConfigureTable,
+        observer: Option<&(dyn Fn(OpcodeClassPlain) + Sync)>,
+    ) -> Result<(), EtableError> {
+        macro_rules! assign_advice {
+            ($ctx:expr, $cell:ident, $value:expr) => {
+                self.config
+                    .common_config
+                    .$cell
+                    .assign($ctx, $value)
+                    .unwrap()
+            };
+        }
+
+        macro_rules! assign_advice_cell {
+            ($ctx:expr, $cell:ident, $value:expr) => {
+                $cell.assign($ctx, $value).unwrap()
+            };
+        }
+
+        let op_configs = &self.config.op_configs;
+        let instruction = entry.eentry.get_instruction(itable);
+
+        let step_status = StepStatus {
+            current: current_status,
+            next: next_status,
+            configure_table,
+        };
+
+        {
+            let class: OpcodeClassPlain = (&instruction.opcode).into();
+
+            let ops = &self.config.common_config.ops;
+            let op = *ops
+                .get(class.index())
+                .ok_or(EtableError::OpSelectorOutOfRange {
+                    class,
+                    index: class.index(),
+                    len: ops.len(),
+                })?;
+            assign_advice_cell!(ctx, op, F::one());
+
+            if let Some(observer) = observer {
+                observer(class);
+            }
+        }
+
+        assign_advice!(ctx, enabled_cell, F::one());
+        assign_advice!(
+            ctx,
+            rest_mops_cell,
+            checked_u32_to_field(current_status.rest_mops, self.common_range_max)?
+        );
+        assign_advice!(ctx, itable_lookup_cell, bn_to_field(&instruction.encode));
+        assign_advice!(
+            ctx,
+            jops_cell,
+            checked_bn_to_field(&current_status.jops, &self.modulus)?
+        );
+
+        {
+            let class: OpcodeClassPlain = (&instruction.opcode).into();
+
+            if let Some(hook) = self.op_assign_hook.as_ref() {
+                hook(&OpAssignEvent {
+                    class,
+                    eid: current_status.eid,
+                    next_eid: next_status.eid,
+                    sp: current_status.sp,
+                    next_sp: next_status.sp,
+                    rest_mops: current_status.rest_mops,
+                });
+            }
+
+            let op_config = op_configs.get(&class).unwrap();
+            op_config.0.assign(ctx, &step_status, entry).unwrap();
+        }
+
+        // Be careful, the function will step context.
+        self.assign_step_state(
+            ctx,
+            &status_to_init_state(current_status, entry, configure_table),
+        )?;
+
+        Ok(())
+    }
+
+    /// Assign the step of a single entry at `offset` of `region` with its (current, next)
+    /// status, for unit testing the assignment of an opcode config without an event table.
+    #[cfg(test)]
+    pub fn assign_single_entry(
+        &self,
+        region: &Region<'_, F>,
+        offset: usize,
+        entry: &EventTableEntryWithMemoryInfo,
+        current: &Status,
+        next: &Status,
+        configure_table: &ConfigureTable,
+    ) -> Result<(), EtableError> {
+        let mut ctx = Context::new(region);
+        ctx.step(offset);
+
+        self.assign_entry_at(
+            &mut ctx,
+            current.itable,
+            entry,
+            current,
+            next,
+            configure_table,
+            None,
+        )
+    }
+
+    /// Assign event table entries with a precomputed status sequence(see `status_sequence`),
+    /// which must contain the terminal status.
+    ///
+    /// `observer` is invoked with the opcode class of each assigned entry. It's called from the
+    /// parallel section, hence in no particular order.
+    pub fn assign_entries_with_statuses(
+        &self,
+        region: &Region<'_, F>,
+        base_offset: usize,
+        itable: &InstructionTable,
+        event_table: &dyn EventTableEntries,
+        configure_table: &ConfigureTable,
+        status: &[Status],
+        observer: Option<&(dyn Fn(OpcodeClassPlain) + Sync)>,
+    ) -> Result<(), EtableError> {
+        if status.len() != event_table.len() + 1 {
+            return Err(EtableError::StatusLengthMismatch {
+                expected: event_table.len() + 1,
+                actual: status.len(),
+            });
+        }
+
+        let assign_entry =
+            |(index, entry): (usize, &EventTableEntryWithMemoryInfo)| -> Result<(), EtableError> {
+                let mut ctx = Context::new(region);
+                ctx.step(Self::entry_offset(base_offset, index)?);
+
+                let current_status =
+                    status
+                        .get(index)
+                        .ok_or(EtableError::StatusIndexOutOfRange {
+                            index,
+                            len: status.len(),
+                        })?;
+                let next_status =
+                    status
+                        .get(index + 1)
+                        .ok_or(EtableError::StatusIndexOutOfRange {
+                            index: index + 1,
+                            len: status.len(),
+                        })?;
+
+                self.assign_entry_at(
+                    &mut ctx,
+                    itable,
+                    entry,
+                    current_status,
+                    next_status,
+                    configure_table,
+                    observer,
+                )
+            };
+
+        // Keep where the assignment stops along with the state before it for diagnosis.
+        // The memory info is resolved here if lazy, see `EventTableEntries`.
+        let assign_entry_with_diagnostics = |index: usize| {
+            let entry = event_table.entry(index);
+
+            assign_entry((index, &*entry)).map_err(|source| EtableError::EntryAssignment {
+                index,
+                eid: entry.eentry.eid,
+                status: status
+                    .get(index)
+                    .or(status.last())
+                    .map(|current| Box::new(StatusRecord::from(current))),
+                source: Box::new(source),
+            })
+        };
+
+        if self.deterministic || event_table.len() < self.parallel_threshold {
+            (0..event_table.len()).try_for_each(assign_entry_with_diagnostics)
+        } else {
+            // Report the failing entry of the lowest index, as the serial assignment does.
+            (0..event_table.len())
+                .into_par_iter()
+                .map(assign_entry_with_diagnostics)
+                .find_first(|result| result.is_err())
+                .unwrap_or(Ok(()))
+        }
+    }
+
+    /// Deterministic seed of the slice derived from its pre-state. Any randomness introduced into
+    /// the assignment must be drawn from it, so that re-assigning a slice is bit-identical.
+    pub fn slice_seed(initialization_state: &InitializationState<u32, BigUint>) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+
+        for field in initialization_state
+            .map(|v| BigUint::from(*v), |v| v.clone())
+            .plain()
+        {
+            let bytes = field.to_bytes_le();
+
+            hasher.update((bytes.len() as u32).to_le_bytes());
+            hasher.update(bytes);
+        }
+
+        hasher.finalize().into()
+    }
+
+    /// Digest of what a verifier observes of the slice: the pre state, the derived post state
+    /// (including the host and context counters), rest_mops and the number of host public inputs.
+    /// Two traces with the same digest are indistinguishable through the public interface.
+    pub fn public_interface_digest(
+        &self,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        configure_table: &ConfigureTable,
+        initialization_state: PreState<'_>,
+        next_event_entry: Option<&EventTableEntry>,
+    ) -> [u8; 32] {
+        let post_initialization_state = self.derive_post_state(
+            event_table,
+            configure_table,
+            initialization_state,
+            next_event_entry,
+        );
+        let (rest_mops, _) = self.compute_rest_mops_and_jops(
+            self.config.op_configs.clone(),
+            itable,
+            event_table,
+            initialization_state,
+        );
+
+        let mut hasher = Sha256::new();
+
+        hasher.update(Self::slice_seed(&initialization_state));
+        hasher.update(Self::slice_seed(&post_initialization_state));
+        hasher.update(rest_mops.to_le_bytes());
+        hasher.update(self.host_input_count(itable, event_table).to_le_bytes());
+
+        hasher.finalize().into()
+    }
+
+    /// Summarize the slice into a `ProofManifest`.
+    pub fn proof_manifest(
+        &self,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        memory_writing_table: &MemoryWritingTable,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+    ) -> Result<ProofManifest, EtableError> {
+        let (rest_mops, _) = self.compute_rest_mops_and_jops(
+            self.config.op_configs.clone(),
+            itable,
+            event_table,
+            initialization_state,
+        );
+
+        let terminal_jops = self
+            .status_sequence(
+                itable,
+                event_table,
+                initialization_state,
+                post_initialization_state,
+            )?
+            .pop()
+            .map_or(BigUint::from(0u64), |status| status.jops);
+
+        Ok(ProofManifest {
+            pre_state_digest: Self::slice_seed(&initialization_state),
+            post_state_digest: Self::slice_seed(&post_initialization_state),
+            entries: event_table.0.len(),
+            rest_mops,
+            terminal_jops,
+            index_ranges: slice_index_ranges(&initialization_state, &post_initialization_state),
+            memory_commitment: memory_writing_table.commitment::<Sha256>().into(),
+        })
+    }
+
+    /// Whether the whole trace can be proven by a single slice: it fits in the capability,
+    /// rest_mops terminates at zero and, without continuation, the jops starting from
+    /// `rest_jops`, the one of the frame table, terminates at zero. With continuation jops
+    /// accumulates across slices and has no terminal value to check.
+    pub fn fits_single_proof(
+        &self,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        initialization_state: PreState<'_>,
+        _rest_jops: &BigUint,
+    ) -> bool {
+        #[cfg(not(feature = "continuation"))]
+        if self
+            .validate_rest_jops_sequence(itable, event_table, _rest_jops)
+            .is_err()
+        {
+            return false;
+        }
+
+        self.fits(event_table)
+            && self
+                .validate_rest_mops_sequence(itable, event_table, initialization_state)
+                .is_ok()
+    }
+
+    pub fn fits(&self, event_table: &dyn EventTableEntries) -> bool {
+        event_table.len() <= self.capability
+    }
+
+    // Negative if the event table exceeds the capability.
+    pub fn remaining_capacity(&self, event_table: &EventTableWithMemoryInfo) -> i64 {
+        self.capability as i64 - event_table.0.len() as i64
+    }
+
+    /// Assign the event table into `region` starting from `base_offset`, the returned cells refer
+    /// to the absolute offsets within the region.
+    ///
+    /// If `checked`, verify that the assignment ends right after the terminal row group and that
+    /// the post state ends at the eid following the last entry. The status sequence is computed
+    /// unless `status` is given, see `status_sequence`.
+    pub(in crate::circuits) fn assign_into(
+        &self,
+        region: &Region<'_, F>,
+        base_offset: usize,
+        itable: &InstructionTable,
+        event_table: &dyn EventTableEntries,
+        configure_table: &ConfigureTable,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+        checked: bool,
+        status: Option<&[Status]>,
+    ) -> Result<EventTablePermutationCells<F>, EtableError> {
+        self.check_trace_size(event_table.len())?;
+
+        if !self.fits(event_table) {
+            return Err(EtableError::CapabilityExceeded {
+                entries: event_table.len(),
+                capability: self.capability,
+            });
+        }
+
+        // Execution moves forward across the slice.
+        for (field, pre, post) in [
+            (
+                "eid",
+                initialization_state.eid,
+                post_initialization_state.eid,
+            ),
+            (
+                "host_public_inputs",
+                initialization_state.host_public_inputs,
+                post_initialization_state.host_public_inputs,
+            ),
+            (
+                "context_in_index",
+                initialization_state.context_in_index,
+                post_initialization_state.context_in_index,
+            ),
+            (
+                "context_out_index",
+                initialization_state.context_out_index,
+                post_initialization_state.context_out_index,
+            ),
+            (
+                "external_host_call_call_index",
+                initialization_state.external_host_call_call_index,
+                post_initialization_state.external_host_call_call_index,
+            ),
+        ] {
+            if post < pre {
+                return Err(EtableError::NonMonotonicState { field, pre, post });
+            }
+        }
+
+        // The memory configuration is shared by the whole run.
+        if initialization_state.maximal_memory_pages
+            != post_initialization_state.maximal_memory_pages
+            || initialization_state.maximal_memory_pages != configure_table.maximal_memory_pages
+        {
+            return Err(EtableError::MaximalMemoryPagesMismatch {
+                pre: initialization_state.maximal_memory_pages,
+                post: post_initialization_state.maximal_memory_pages,
+                configured: configure_table.maximal_memory_pages,
+            });
+        }
+
+        // An empty slice, e.g. the one built without witness, leaves the state unchanged.
+        if event_table.is_empty() && *initialization_state != *post_initialization_state {
+            return Err(EtableError::EmptySliceStateChanged);
+        }
+
+        let mut ctx = Context::new(region);
+
+        debug!("size of execution table: {}", event_table.len());
+
+        ctx.step(base_offset);
+        self.init(&mut ctx)?;
+        ctx.reset();
+
+        ctx.step(base_offset);
+        let pre_initialization_state = self.assign_step_state(&mut ctx, &initialization_state)?;
+        ctx.reset();
+
+        ctx.step(base_offset);
+        let (rest_mops_cell, _jops_cell) = self.assign_rest_ops_first_step(&mut ctx)?;
+
+        let (rest_mops, jops) = self.compute_rest_mops_and_jops(
+            self.config.op_configs.clone(),
+            itable,
+            event_table,
+            initialization_state,
+        );
+
+        Self::validate_memory_pages(event_table, configure_table)?;
+
+        self.assign_entries(
+            region,
+            base_offset,
+            itable,
+            event_table,
+            configure_table,
+            initialization_state,
+            post_initialization_state,
+            rest_mops,
+            jops,
+            status,
+        )?;
+        ctx.step(Self::entry_offset(0, event_table.len())?);
+
+        let post_initialization_state_cells = self.assign_padding_and_post_initialization_state(
+            &mut ctx,
+            base_offset,
+            post_initialization_state,
+        )?;
+
+        if checked {
+            let expected = base_offset + (self.capability + 1) * EVENT_TABLE_ENTRY_ROWS as usize;
+
+            if ctx.offset != expected {
+                return Err(EtableError::RowCountMismatch {
+                    expected,
+                    actual: ctx.offset,
+                });
+            }
+
+            // Each entry takes exactly one eid.
+            let expected = initialization_state.eid + event_table.len() as u32;
+            if post_initialization_state.eid != expected {
+                return Err(EtableError::TerminalEidMismatch {
+                    expected,
+                    actual: post_initialization_state.eid,
+                });
+            }
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "continuation")] {
+                Ok(EventTablePermutationCells {
+                    rest_mops: rest_mops_cell,
+                    rest_jops: None,
+                    pre_initialization_state,
+                    post_initialization_state: post_initialization_state_cells,
+                    plain_rest_mops: rest_mops,
+                    plain_states: (
+                        initialization_state.0.clone(),
+                        post_initialization_state.0.clone(),
+                    ),
+                })
+            } else {
+                Ok(EventTablePermutationCells {
+                    rest_mops: rest_mops_cell,
+                    rest_jops: Some(_jops_cell),
+                    pre_initialization_state,
+                    post_initialization_state: post_initialization_state_cells,
+                    plain_rest_mops: rest_mops,
+                    plain_states: (
+                        initialization_state.0.clone(),
+                        post_initialization_state.0.clone(),
+                    ),
+                })
+            }
+        }
+    }
+
+    /// Passing `EventTableWithMemoryInfo::empty()` with identical pre and post states assigns the
+    /// shape of the table only, which is the key generation path.
+    pub(in crate::circuits) fn assign(
+        &self,
+        layouter: impl Layouter<F>,
+        itable: &InstructionTable,
+        event_table: &dyn EventTableEntries,
+        configure_table: &ConfigureTable,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+        is_last_slice: bool,
+    ) -> Result<EventTablePermutationCells<F>, EtableError> {
+        self.assign_with_status(
+            layouter,
+            itable,
+            event_table,
+            configure_table,
+            initialization_state,
+            post_initialization_state,
+            is_last_slice,
+            None,
+        )
+    }
+
+    // Same as `assign` with an optional precomputed status sequence.
+    fn assign_with_status(
+        &self,
+        layouter: impl Layouter<F>,
+        itable: &InstructionTable,
+        event_table: &dyn EventTableEntries,
+        configure_table: &ConfigureTable,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+        _is_last_slice: bool,
+        status: Option<&[Status]>,
+    ) -> Result<EventTablePermutationCells<F>, EtableError> {
+        // The region closure can only return halo2's error, keep the original one here.
+        let etable_error = Mutex::new(None);
+
+        let cells = layouter.assign_region(
+            || "event table",
+            |region| {
+                self.assign_into(
                     region,
-                    self.config.op_configs.clone(),
+                    0,
                     itable,
                     event_table,
                     configure_table,
-                    &initialization_state,
+                    initialization_state,
                     post_initialization_state,
-                    rest_mops,
-                    jops,
-                )?;
-                ctx.step(EVENT_TABLE_ENTRY_ROWS as usize * event_table.0.len());
-
-                let post_initialization_state_cells = self
-                    .assign_padding_and_post_initialization_state(
-                        &mut ctx,
-                        &post_initialization_state,
-                    )?;
-
-                cfg_if::cfg_if! {
-                    if #[cfg(feature = "continuation")] {
-                        Ok(EventTablePermutationCells {
-                            rest_mops: rest_mops_cell,
-                            rest_jops: None,
-                            pre_initialization_state,
-                            post_initialization_state: post_initialization_state_cells,
-                        })
-                    } else {
-                        Ok(EventTablePermutationCells {
-                            rest_mops: rest_mops_cell,
-                            rest_jops: Some(_jops_cell),
-                            pre_initialization_state,
-                            post_initialization_state: post_initialization_state_cells,
-                        })
-                    }
-                }
+                    self.checked,
+                    status,
+                )
+                .map_err(|err| {
+                    *etable_error.lock().unwrap() = Some(err);
+
+                    Error::Synthesis
+                })
             },
+        );
+
+        let cells = match etable_error.into_inner().unwrap() {
+            Some(err) => Err(err),
+            None => Ok(cells?),
+        }?;
+
+        if let Some(counters) = self.counters.as_ref() {
+            counters.record(event_table.len());
+        }
+
+        let entries = event_table.len();
+        if self.fill_ratio(entries) > self.fill_warning_threshold {
+            warn!(
+                "etable: {} entries take more than {} of the capability {}, consider increasing k or reducing the slice size.",
+                entries, self.fill_warning_threshold, self.capability
+            );
+        }
+
+        Ok(cells)
+    }
+
+    // Guarded for a zero capability, which would be NaN otherwise.
+    fn fill_ratio(&self, entries: usize) -> f64 {
+        if self.capability == 0 {
+            0.0
+        } else {
+            entries as f64 / self.capability as f64
+        }
+    }
+
+    /// Same as `assign` for proving a trace, rejecting an empty event table which `assign` would
+    /// take as the key generation path.
+    pub fn assign_with_witness(
+        &self,
+        layouter: impl Layouter<F>,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        configure_table: &ConfigureTable,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+        is_last_slice: bool,
+    ) -> Result<EventTablePermutationCells<F>, EtableError> {
+        if event_table.0.is_empty() {
+            return Err(EtableError::MissingWitness);
+        }
+
+        self.assign(
+            layouter,
+            itable,
+            event_table,
+            configure_table,
+            initialization_state,
+            post_initialization_state,
+            is_last_slice,
+        )
+    }
+
+    /// Same as `assign` for key generation, which expects `EventTableWithMemoryInfo::empty()`.
+    pub fn assign_for_keygen(
+        &self,
+        layouter: impl Layouter<F>,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        configure_table: &ConfigureTable,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+        is_last_slice: bool,
+    ) -> Result<EventTablePermutationCells<F>, EtableError> {
+        if !event_table.0.is_empty() {
+            return Err(EtableError::UnexpectedWitness {
+                entries: event_table.0.len(),
+            });
+        }
+
+        self.assign(
+            layouter,
+            itable,
+            event_table,
+            configure_table,
+            initialization_state,
+            post_initialization_state,
+            is_last_slice,
         )
     }
+
+    /// Assign several independent slices, each one into its own region.
+    ///
+    /// The status sequences of the slices are computed in parallel, while the regions are
+    /// assigned one after another since each of them borrows `layouter` mutably.
+    pub fn assign_batch(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        slices: &[SliceInput],
+    ) -> Result<Vec<EventTablePermutationCells<F>>, EtableError> {
+        let statuses = slices
+            .par_iter()
+            .map(|slice| {
+                self.status_sequence(
+                    slice.itable,
+                    slice.event_table,
+                    slice.initialization_state,
+                    slice.post_initialization_state,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        slices
+            .iter()
+            .zip(statuses.iter())
+            .enumerate()
+            .map(|(index, (slice, status))| {
+                self.assign_with_status(
+                    layouter.namespace(|| format!("event table slice {}", index)),
+                    slice.itable,
+                    slice.event_table,
+                    slice.configure_table,
+                    slice.initialization_state,
+                    slice.post_initialization_state,
+                    slice.is_last_slice,
+                    Some(status),
+                )
+            })
+            .collect()
+    }
+
+    /// Same as `assign`, along with how much of the capability the slice takes.
+    pub fn assign_with_stats(
+        &self,
+        layouter: impl Layouter<F>,
+        itable: &InstructionTable,
+        event_table: &EventTableWithMemoryInfo,
+        configure_table: &ConfigureTable,
+        initialization_state: PreState<'_>,
+        post_initialization_state: PostState<'_>,
+        is_last_slice: bool,
+    ) -> Result<(EventTablePermutationCells<F>, AssignStats), EtableError> {
+        let cells = self.assign(
+            layouter,
+            itable,
+            event_table,
+            configure_table,
+            initialization_state,
+            post_initialization_state,
+            is_last_slice,
+        )?;
+
+        let entries = event_table.0.len();
+
+        Ok((
+            cells,
+            AssignStats {
+                entries,
+                capability: self.capability,
+                rows_used: entries * EVENT_TABLE_ENTRY_ROWS as usize,
+                fill_ratio: self.fill_ratio(entries),
+            },
+        ))
+    }
 }
@@ -1,15 +1,21 @@
 use self::allocator::*;
+use self::assign::AssignmentCounters;
+use self::assign::OpAssignEvent;
 use self::constraint_builder::ConstraintBuilder;
 use super::bit_table::BitTableConfig;
 use super::cell::*;
+use super::config::common_range_max;
+use super::config::zkwasm_k;
 use super::external_host_call_table::ExternalHostCallTableConfig;
 use super::image_table::ImageTableConfig;
 use super::jtable::JumpTableConfig;
 use super::mtable::MemoryTableConfig;
 use super::rtable::RangeTableConfig;
 use super::traits::ConfigureLookupTable;
+use super::utils::field_modulus;
 use super::utils::step_status::StepStatus;
 use super::utils::table_entry::EventTableEntryWithMemoryInfo;
+use super::utils::table_entry::EventTableWithMemoryInfo;
 use super::utils::Context;
 use crate::circuits::etable::op_configure::op_bin::BinConfigBuilder;
 use crate::circuits::etable::op_configure::op_bin_bit::BinBitConfigBuilder;
@@ -39,6 +45,7 @@ use crate::circuits::etable::op_configure::op_store::StoreConfigBuilder;
 use crate::circuits::etable::op_configure::op_test::TestConfigBuilder;
 use crate::circuits::etable::op_configure::op_unary::UnaryConfigBuilder;
 use crate::constant_from;
+use crate::error::EtableError;
 use crate::fixed_curr;
 use crate::foreign::context::etable_op_configure::ETableContextHelperTableConfigBuilder;
 use crate::foreign::require_helper::etable_op_configure::ETableRequireHelperTableConfigBuilder;
@@ -58,12 +65,13 @@ use num_bigint::BigUint;
 use num_traits::Zero;
 use specs::encode::instruction_table::encode_instruction_table_entry;
 use specs::etable::EventTableEntry;
+use specs::itable::InstructionTable;
 use specs::itable::OpcodeClass;
 use specs::itable::OpcodeClassPlain;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
-pub(super) mod assign;
+pub mod assign;
 mod op_configure;
 
 pub(crate) mod allocator;
@@ -662,6 +670,78 @@ impl<F: FieldExt> EventTableConfig<F> {
             op_configs: Arc::new(op_configs),
         }
     }
+
+    /// The advice column of `enabled_cell` and its rotation within a step, for reading the
+    /// assignment back, e.g. from a MockProver.
+    pub fn enabled_cell_column(&self) -> (Column<Advice>, i32) {
+        let cell = self.common_config.enabled_cell.cell;
+
+        (cell.col, cell.rot)
+    }
+
+    /// Number of the steps of `capability` whose `enabled_cell` is one, reading the cell at a row
+    /// relative to the start of the region by `value_at(column, row)`. It equals the number of
+    /// entries of the assigned event table.
+    ///
+    /// Panics if an enabled step follows a disabled one, i.e. the real entries are not contiguous
+    /// from the start of the region.
+    pub fn count_enabled_cells(
+        &self,
+        capability: usize,
+        value_at: impl Fn(Column<Advice>, usize) -> F,
+    ) -> usize {
+        let (col, rot) = self.enabled_cell_column();
+
+        let enabled = (0..capability)
+            .map(|step| value_at(col, step * EVENT_TABLE_ENTRY_ROWS as usize + rot as usize))
+            .collect::<Vec<_>>();
+
+        let count = enabled
+            .iter()
+            .take_while(|value| **value == F::one())
+            .count();
+
+        assert!(
+            enabled[count..].iter().all(|value| *value == F::zero()),
+            "etable: enabled steps are not contiguous from the start, the first {} are followed by a disabled one",
+            count
+        );
+
+        count
+    }
+
+    /// Assert that `step_sel` over the first `rows` rows of the region, read by
+    /// `value_at(column, row)`, selects the first row of each of `expected` steps and nothing
+    /// else, i.e. `init` fills it for a capability of `expected`.
+    pub fn assert_step_sel_count(
+        &self,
+        expected: usize,
+        rows: usize,
+        value_at: impl Fn(Column<Fixed>, usize) -> F,
+    ) {
+        let step_rows = EVENT_TABLE_ENTRY_ROWS as usize;
+
+        assert!(
+            rows >= expected * step_rows,
+            "etable: {} rows can't hold {} steps",
+            rows,
+            expected
+        );
+
+        for row in 0..rows {
+            let selected = row % step_rows == 0 && row / step_rows < expected;
+            let value = value_at(self.step_sel, row);
+
+            assert!(
+                value == if selected { F::one() } else { F::zero() },
+                "etable: step_sel at row {} is {:?}, {} is expected for a capability of {}",
+                row,
+                value,
+                selected as u8,
+                expected
+            );
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -669,6 +749,29 @@ pub struct EventTableChip<F: FieldExt> {
     config: EventTableConfig<F>,
     // The maximal number of entries(which sel = 1) of etable
     capability: usize,
+    // Entries are assigned serially if fewer than it
+    parallel_threshold: usize,
+    // Entries are always assigned serially, for stable timing
+    deterministic: bool,
+    // Zero fields of the padding are left unassigned
+    sparse_padding: bool,
+    // The layout of the assignment is verified by `assign`
+    checked: bool,
+    counters: Option<Arc<AssignmentCounters>>,
+    // Invoked before the opcode config assignment of each entry
+    op_assign_hook: Option<Arc<dyn Fn(&OpAssignEvent) + Send + Sync>>,
+    // Bit width jops of every step must fit in
+    max_jops_bits: Option<u64>,
+    // Rows reserved after the post initialization state, for alignment with the following region
+    extra_reserved_rows: usize,
+    // Fill ratio above which assigning a slice warns
+    fill_warning_threshold: f64,
+    // Traces with more entries are rejected
+    max_entries: Option<usize>,
+    // Modulus of F, for checking values before converting them to field
+    modulus: BigUint,
+    // Upper bound of the common range cells, e.g. rest_mops
+    common_range_max: u64,
 }
 
 impl<F: FieldExt> EventTableChip<F> {
@@ -679,6 +782,177 @@ impl<F: FieldExt> EventTableChip<F> {
     ) -> Self {
         assert!(capability * EVENT_TABLE_ENTRY_ROWS as usize <= max_available_rows);
 
-        Self { config, capability }
+        Self {
+            config,
+            capability,
+            parallel_threshold: 0,
+            deterministic: false,
+            sparse_padding: false,
+            checked: cfg!(debug_assertions),
+            counters: None,
+            op_assign_hook: None,
+            max_jops_bits: None,
+            extra_reserved_rows: 0,
+            fill_warning_threshold: 0.9,
+            max_entries: None,
+            modulus: field_modulus::<F>(),
+            common_range_max: common_range_max(zkwasm_k()) as u64,
+        }
+    }
+
+    /// Same as `new` with the largest capability fitting in both `2^k` and `max_available_rows`.
+    pub fn new_with_max_capability(
+        config: EventTableConfig<F>,
+        k: u32,
+        max_available_rows: usize,
+    ) -> Self {
+        let capability = usize::min(
+            Self::max_capability_for_k(k).expect("2^k rows can't hold the reserved rows"),
+            max_available_rows / EVENT_TABLE_ENTRY_ROWS as usize,
+        );
+
+        Self::new(config, capability, max_available_rows)
+    }
+
+    /// The largest capability whose step rows and reserved rows fit in `2^k` rows, `None` if
+    /// `2^k` overflows or is fewer than the reserved rows.
+    pub fn max_capability_for_k(k: u32) -> Option<usize> {
+        let rows = 1usize.checked_shl(k)?.checked_sub(Self::reserved_rows())?;
+
+        Some(rows / EVENT_TABLE_ENTRY_ROWS as usize)
+    }
+
+    /// Rows reserved after the `capability` step rows, i.e. the row group holding the post
+    /// initialization state and the terminating rest_mops/jops.
+    pub fn reserved_rows() -> usize {
+        EVENT_TABLE_ENTRY_ROWS as usize
+    }
+
+    /// Extend the region by `rows` rows after the reserved rows, e.g. to align the end of the
+    /// region with a following sub-circuit.
+    pub fn with_extra_reserved_rows(mut self, rows: usize) -> Self {
+        self.extra_reserved_rows = rows;
+        self
+    }
+
+    /// The number of rows the region of the event table occupies.
+    pub fn region_rows(&self) -> usize {
+        self.capability * EVENT_TABLE_ENTRY_ROWS as usize
+            + Self::reserved_rows()
+            + self.extra_reserved_rows
+    }
+
+    /// Warn when a slice takes more than `threshold` of the capability, 0.9 by default, ahead of
+    /// a slightly longer trace overflowing it.
+    pub fn with_fill_warning_threshold(mut self, threshold: f64) -> Self {
+        self.fill_warning_threshold = threshold;
+        self
+    }
+
+    /// Reject traces of more than `max` entries with `EtableError::TraceTooLarge`, e.g. for
+    /// untrusted inputs. Disabled by default.
+    pub fn with_max_entries(mut self, max: usize) -> Self {
+        self.max_entries = Some(max);
+        self
+    }
+
+    /// Check a trace of `len` entries against `with_max_entries`, cheap enough to run before
+    /// building the `EventTableWithMemoryInfo` of the trace.
+    pub fn check_trace_size(&self, len: usize) -> Result<(), EtableError> {
+        match self.max_entries {
+            Some(max) if len > max => Err(EtableError::TraceTooLarge { len, max }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject traces whose jops exceeds `bits` at any step, in addition to the field check.
+    pub fn with_max_jops_bits(mut self, bits: u64) -> Self {
+        self.max_jops_bits = Some(bits);
+        self
+    }
+
+    /// Call `hook` before assigning the opcode config of each entry, e.g. to log the inputs of
+    /// the assignment. It may be called from several threads at once.
+    pub fn with_op_assign_hook(mut self, hook: Arc<dyn Fn(&OpAssignEvent) + Send + Sync>) -> Self {
+        self.op_assign_hook = Some(hook);
+        self
+    }
+
+    /// Accumulate the statistics of every successful `assign` into `counters`.
+    pub fn with_counters(mut self, counters: Arc<AssignmentCounters>) -> Self {
+        self.counters = Some(counters);
+        self
+    }
+
+    /// Assign event tables with fewer entries than `threshold` serially, where the parallel
+    /// overhead exceeds the benefit. The assignment is identical either way.
+    pub fn with_parallel_threshold(mut self, threshold: usize) -> Self {
+        self.parallel_threshold = threshold;
+        self
+    }
+
+    /// Returns the classes of `OpcodeClassPlain::all` without a config, which would otherwise
+    /// panic only when assigning a trace using them.
+    pub fn verify_op_configs_complete(&self) -> Result<(), Vec<OpcodeClassPlain>> {
+        let missing = OpcodeClassPlain::all()
+            .into_iter()
+            .filter(|class| !self.config.op_configs.contains_key(class))
+            .collect::<Vec<_>>();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
+    /// Eids and classes of the entries of `event_table` without an op config, whose assignment
+    /// would otherwise panic.
+    pub fn unsupported_opcodes(
+        &self,
+        event_table: &EventTableWithMemoryInfo,
+        itable: &InstructionTable,
+    ) -> Vec<(u32, OpcodeClassPlain)> {
+        event_table
+            .0
+            .iter()
+            .filter_map(|entry| {
+                let class: OpcodeClassPlain = (&entry.eentry.get_instruction(itable).opcode).into();
+
+                (!self.config.op_configs.contains_key(&class)).then_some((entry.eentry.eid, class))
+            })
+            .collect()
+    }
+
+    /// Assign entries serially in a single pass so that the timing of runs is comparable, e.g. for
+    /// profiling. The assignment is identical to the parallel one.
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Skip assigning the padding fields whose value is zero, since cells default to zero. The
+    /// post initialization state is still fully assigned for the permutation. MockProver may
+    /// report the skipped cells as unassigned.
+    pub fn with_sparse_padding(mut self, sparse_padding: bool) -> Self {
+        self.sparse_padding = sparse_padding;
+        self
+    }
+
+    /// Verify the row count and terminal eid of every `assign`, see `assign_into`. Enabled by
+    /// default in debug builds.
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// Opcode classes supported by the chip with their configs, ordered by class.
+    pub fn op_configs_iter(
+        &self,
+    ) -> impl Iterator<Item = (OpcodeClassPlain, &dyn EventTableOpcodeConfig<F>)> + '_ {
+        self.config
+            .op_configs
+            .iter()
+            .map(|(class, config)| (*class, config.0.as_ref()))
     }
 }
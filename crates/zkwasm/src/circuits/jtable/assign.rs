@@ -8,6 +8,7 @@ use specs::jtable::StaticFrameEntry;
 use specs::jtable::STATIC_FRAME_ENTRY_NUMBER;
 
 use super::encode_jops;
+use super::frame_table_rest_jops;
 use super::JtableOffset;
 use super::JumpTableChip;
 use crate::circuits::utils::bn_to_field;
@@ -195,12 +196,7 @@ impl<F: FieldExt> JumpTableChip<F> {
                 self.init(&mut ctx)?;
                 ctx.reset();
 
-                // non-static entry includes `call`` and `return`` op, static entry only includes `return` op
-                let mut rest_jops = encode_jops(
-                    jtable.entries().len() as u32
-                        + static_entries.iter().filter(|entry| entry.enable).count() as u32,
-                    jtable.entries().len() as u32,
-                );
+                let mut rest_jops = frame_table_rest_jops(static_entries, jtable);
 
                 let rest_jopss = self.assign_first_rest_jops(&mut ctx, rest_jops.clone())?;
                 let cells_to_permutation = self.assign_static_entries_and_first_rest_jops(
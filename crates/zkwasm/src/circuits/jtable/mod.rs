@@ -6,6 +6,8 @@ use halo2_proofs::plonk::ConstraintSystem;
 use halo2_proofs::plonk::Fixed;
 use num_bigint::BigUint;
 use num_bigint::ToBigUint;
+use specs::jtable::JumpTable;
+use specs::jtable::StaticFrameEntry;
 use specs::jtable::STATIC_FRAME_ENTRY_NUMBER;
 use std::marker::PhantomData;
 
@@ -23,6 +25,19 @@ pub fn encode_jops(return_instructions: u32, call_instructions: u32) -> BigUint
         | call_instructions.to_biguint().unwrap()
 }
 
+/// The rest jops the frame table starts from, which the event table must consume.
+pub fn frame_table_rest_jops(
+    static_entries: &[StaticFrameEntry; STATIC_FRAME_ENTRY_NUMBER],
+    jtable: &JumpTable,
+) -> BigUint {
+    // non-static entry includes `call`` and `return`` op, static entry only includes `return` op
+    encode_jops(
+        jtable.entries().len() as u32
+            + static_entries.iter().filter(|entry| entry.enable).count() as u32,
+        jtable.entries().len() as u32,
+    )
+}
+
 pub enum JtableOffset {
     JtableOffsetEnable = 0,
     JtableOffsetRest = 1,
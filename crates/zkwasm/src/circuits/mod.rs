@@ -9,13 +9,16 @@ use halo2_proofs::plonk::VirtualCells;
 use num_bigint::BigUint;
 use specs::slice::Slice;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
+use self::config::common_range_max;
+use self::etable::EventTableChip;
 use self::etable::EVENT_TABLE_ENTRY_ROWS;
 use self::image_table::compute_maximal_pages;
 use self::zkwasm_circuit::RESERVE_ROWS;
 
 pub(crate) mod cell;
-pub(crate) mod etable;
+pub mod etable;
 
 mod bit_table;
 mod external_host_call_table;
@@ -47,6 +50,12 @@ pub(crate) fn compute_slice_capability(k: u32) -> u32 {
 pub struct ZkWasmCircuit<F: FieldExt> {
     pub k: u32,
     pub slice: Slice,
+    // Common range maximum of the memory writing table, derived from k if None
+    common_range_max: Option<u32>,
+    // Applied to the event table chip before assigning
+    etable_chip_options: Option<Arc<dyn Fn(EventTableChip<F>) -> EventTableChip<F> + Send + Sync>>,
+    // Resolve the memory info of the event table entries while assigning them
+    lazy_memory_info: bool,
     _data: PhantomData<F>,
 }
 
@@ -87,10 +96,52 @@ impl<F: FieldExt> ZkWasmCircuit<F> {
         Ok(ZkWasmCircuit {
             k,
             slice,
+            common_range_max: None,
+            etable_chip_options: None,
+            lazy_memory_info: false,
             _data: PhantomData,
         })
     }
 
+    /// Build the memory writing table with an explicit common range maximum instead of deriving
+    /// it from k, see `MemoryWritingTable::from_with_common_range`. It can't exceed the range
+    /// supported by k.
+    pub fn with_common_range_max(mut self, max: u32) -> Result<Self, BuildingCircuitError> {
+        let limit = common_range_max(self.k);
+
+        if max > limit {
+            return Err(BuildingCircuitError::CommonRangeExceedLimit(
+                max, limit, self.k,
+            ));
+        }
+
+        self.common_range_max = Some(max);
+
+        Ok(self)
+    }
+
+    /// Configure the event table chip through its builders, e.g.
+    /// `|chip| chip.with_max_entries(max)`, before the circuit assigns it.
+    pub fn with_etable_chip_options(
+        mut self,
+        options: impl Fn(EventTableChip<F>) -> EventTableChip<F> + Send + Sync + 'static,
+    ) -> Self {
+        self.etable_chip_options = Some(Arc::new(options));
+        self
+    }
+
+    /// Resolve the memory info of each event table entry when assigning it instead of ahead, see
+    /// `LazyEventTableWithMemoryInfo`. It saves the memory of large traces at the cost of time.
+    pub fn with_lazy_memory_info(mut self, lazy_memory_info: bool) -> Self {
+        self.lazy_memory_info = lazy_memory_info;
+        self
+    }
+
+    // The common range maximum the memory writing table is built with.
+    fn memory_common_range_max(&self) -> u32 {
+        self.common_range_max.unwrap_or(common_range_max(self.k))
+    }
+
     pub fn mock_test(&self, instances: Vec<F>) -> anyhow::Result<()> {
         let prover = MockProver::run(self.k, self, vec![instances])?;
         assert_eq!(prover.verify(), Ok(()));
@@ -339,10 +339,7 @@ impl<F: FieldExt> MemoryTableChip<F> {
                 let mut ctx = Context::new(region);
 
                 debug!("size of memory writing table: {}", mtable.0.len());
-                assert!(
-                    mtable.0.len() * (MEMORY_TABLE_ENTRY_ROWS as usize)
-                        < self.maximal_available_rows
-                );
+                assert!(mtable.predicted_rows() < self.maximal_available_rows);
 
                 let rest_mops = mtable
                     .0
@@ -352,7 +349,7 @@ impl<F: FieldExt> MemoryTableChip<F> {
                 self.assign_fixed(&mut ctx)?;
                 ctx.reset();
 
-                let (rest_memory_finalize_ops, _) = mtable.count_rest_memory_finalize_ops();
+                let rest_memory_finalize_ops = mtable.finalize_op_count();
 
                 #[cfg(feature = "continuation")]
                 let rest_memory_finalize_ops_cell =
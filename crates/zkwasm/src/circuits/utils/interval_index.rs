@@ -0,0 +1,97 @@
+use specs::mtable::LocationType;
+use std::collections::BTreeMap;
+
+use crate::circuits::utils::table_entry::MemoryWritingEntry;
+use crate::circuits::utils::table_entry::MemoryWritingTable;
+
+/// Lifespans `(eid, end_eid]` of a `MemoryWritingTable` indexed per location, i.e. the eids of
+/// the reads observing a write, as resolved by `EventTableWithMemoryInfo::new`.
+///
+/// Lifespans of one location are chained, so they are disjoint and ordered by both ends once
+/// sorted by start. Point and range queries are binary searches over them.
+pub struct IntervalIndex<'a> {
+    locations: BTreeMap<(LocationType, u32), Vec<&'a MemoryWritingEntry>>,
+}
+
+impl<'a> IntervalIndex<'a> {
+    pub fn new(table: &'a MemoryWritingTable) -> Self {
+        let mut locations = BTreeMap::<_, Vec<_>>::new();
+
+        for entry in &table.0 {
+            locations
+                .entry((entry.entry.ltype, entry.entry.offset))
+                .or_default()
+                .push(entry);
+        }
+
+        for lifespans in locations.values_mut() {
+            lifespans.sort_by_key(|entry| entry.entry.eid);
+        }
+
+        IntervalIndex { locations }
+    }
+
+    fn lifespans(&self, ltype: LocationType, offset: u32) -> &[&'a MemoryWritingEntry] {
+        self.locations
+            .get(&(ltype, offset))
+            .map(|lifespans| lifespans.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Whether the location is written by some entry.
+    pub fn contains_location(&self, ltype: LocationType, offset: u32) -> bool {
+        self.locations.contains_key(&(ltype, offset))
+    }
+
+    /// The entry of the location whose lifespan contains `eid`, i.e. the one a read at `eid`
+    /// observes.
+    pub fn query_point(
+        &self,
+        ltype: LocationType,
+        offset: u32,
+        eid: u32,
+    ) -> Option<&'a MemoryWritingEntry> {
+        let lifespans = self.lifespans(ltype, offset);
+        let index = lifespans.partition_point(|entry| entry.end_eid < eid);
+
+        lifespans
+            .get(index)
+            .copied()
+            .filter(|entry| entry.entry.eid < eid)
+    }
+
+    /// The entry of the location written at `eid`.
+    pub fn query_write(
+        &self,
+        ltype: LocationType,
+        offset: u32,
+        eid: u32,
+    ) -> Option<&'a MemoryWritingEntry> {
+        let lifespans = self.lifespans(ltype, offset);
+
+        lifespans
+            .binary_search_by_key(&eid, |entry| entry.entry.eid)
+            .ok()
+            .map(|index| lifespans[index])
+    }
+
+    /// The entries of the location whose lifespans overlap `(start_eid, end_eid]`, ordered by eid.
+    pub fn query_range(
+        &self,
+        ltype: LocationType,
+        offset: u32,
+        start_eid: u32,
+        end_eid: u32,
+    ) -> &[&'a MemoryWritingEntry] {
+        let lifespans = self.lifespans(ltype, offset);
+        let first = lifespans.partition_point(|entry| entry.end_eid <= start_eid);
+        let last = lifespans.partition_point(|entry| entry.entry.eid < end_eid);
+
+        &lifespans[first..usize::max(first, last)]
+    }
+
+    /// Locations present in the index, ordered by location.
+    pub fn locations(&self) -> impl Iterator<Item = &(LocationType, u32)> {
+        self.locations.keys()
+    }
+}
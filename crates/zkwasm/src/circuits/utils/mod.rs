@@ -3,9 +3,12 @@ use halo2_proofs::arithmetic::FieldExt;
 use halo2_proofs::circuit::Region;
 use num_bigint::BigUint;
 
+use crate::error::RangeError;
+
 pub mod bit;
 pub mod common_range;
 pub mod image_table;
+pub mod interval_index;
 pub mod row_diff;
 pub mod step_status;
 pub mod u16;
@@ -63,6 +66,34 @@ pub fn bn_to_field<F: BaseExt>(bn: &BigUint) -> F {
     F::read(&mut bytes).unwrap()
 }
 
+/// The modulus of `F`, computed once for repeated `checked_bn_to_field`.
+pub fn field_modulus<F: FieldExt>() -> BigUint {
+    field_to_bn(&-F::one()) + 1u64
+}
+
+/// Same as `F::from(v as u64)`, rejecting `v` beyond `max`, e.g. the upper bound of a range cell.
+pub fn checked_u32_to_field<F: FieldExt>(v: u32, max: u64) -> Result<F, RangeError> {
+    if v as u64 > max {
+        return Err(RangeError::ExceedsMax {
+            value: v as u64,
+            max,
+        });
+    }
+
+    Ok(F::from(v as u64))
+}
+
+/// Same as `bn_to_field`, rejecting `v` that would wrap around `modulus`. The caller passes the
+/// modulus from `field_modulus` rather than having it recomputed, which serializes a field
+/// element, for every value.
+pub fn checked_bn_to_field<F: FieldExt>(v: &BigUint, modulus: &BigUint) -> Result<F, RangeError> {
+    if v >= modulus {
+        return Err(RangeError::ExceedsModulus { value: v.clone() });
+    }
+
+    Ok(bn_to_field(v))
+}
+
 #[macro_export]
 macro_rules! curr {
     ($meta: expr, $x: expr) => {
@@ -1,6 +1,18 @@
 use num_bigint::BigUint;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::Serializer;
+use sha2::Digest;
+use sha2::Sha256;
 use specs::configure_table::ConfigureTable;
 use specs::itable::InstructionTable;
+use specs::mtable::AccessType;
+use specs::mtable::LocationType;
+use specs::state::InitializationState;
+use std::path::Path;
+
+use crate::circuits::utils::table_entry::EventTableEntryWithMemoryInfo;
+use crate::circuits::utils::table_entry::EventTableWithMemoryInfo;
 
 #[derive(Clone)]
 pub struct Status<'a> {
@@ -22,8 +34,332 @@ pub struct Status<'a> {
     pub itable: &'a InstructionTable,
 }
 
+/// Serialized as its `StatusRecord`.
+impl Serialize for Status<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StatusRecord::from(self).serialize(serializer)
+    }
+}
+
 pub struct StepStatus<'a, 'b> {
     pub current: &'a Status<'b>,
     pub next: &'a Status<'b>,
     pub configure_table: &'b ConfigureTable,
 }
+
+/// The state assigned at the step of `entry`.
+///
+/// `frame_id` of an `InitializationState` and `last_jump_eid` of a `Status`/`EventTableEntry`
+/// name the same quantity: the eid of the call that entered the current frame, or 0 in the
+/// exported entry function. This function maps `last_jump_eid` to `frame_id` and
+/// `terminate_status` of the event table chip maps it back.
+pub fn status_to_init_state(
+    status: &Status,
+    entry: &EventTableEntryWithMemoryInfo,
+    configure_table: &ConfigureTable,
+) -> InitializationState<u32, BigUint> {
+    debug_assert_eq!(
+        status.last_jump_eid, entry.eentry.last_jump_eid,
+        "last_jump_eid of the status diverges from the entry at eid {}",
+        entry.eentry.eid
+    );
+
+    InitializationState {
+        eid: entry.eentry.eid,
+        fid: entry.eentry.fid,
+        iid: entry.eentry.iid,
+        sp: entry.eentry.sp,
+        frame_id: entry.eentry.last_jump_eid,
+
+        host_public_inputs: status.host_public_inputs,
+        context_in_index: status.context_in_index,
+        context_out_index: status.context_out_index,
+        external_host_call_call_index: status.external_host_call_call_index,
+
+        initial_memory_pages: entry.eentry.allocated_memory_pages,
+        maximal_memory_pages: configure_table.maximal_memory_pages,
+
+        #[cfg(feature = "continuation")]
+        jops: status.jops.clone(),
+
+        #[cfg(not(feature = "continuation"))]
+        _phantom: core::marker::PhantomData,
+    }
+}
+
+/// Render a status sequence as csv, one row per status with a header of the field names.
+pub fn status_sequence_to_csv(statuses: &[Status]) -> String {
+    let mut lines = vec![StatusRecord::CSV_HEADER.join(",")];
+
+    lines.extend(
+        statuses
+            .iter()
+            .map(|status| StatusRecord::from(status).to_csv_row().join(",")),
+    );
+
+    lines.join("\n")
+}
+
+/// Identifies the instruction table a persisted status sequence refers to.
+pub fn itable_digest(itable: &InstructionTable) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+
+    for entry in itable.iter() {
+        hasher.update(entry.fid.to_le_bytes());
+        hasher.update(entry.iid.to_le_bytes());
+        hasher.update(entry.encode.to_bytes_le());
+    }
+
+    hasher.finalize().into()
+}
+
+/// `Status` without the instruction table, which is reattached when loading.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusRecord {
+    pub eid: u32,
+    pub fid: u32,
+    pub iid: u32,
+    pub sp: u32,
+    pub last_jump_eid: u32,
+    pub allocated_memory_pages: u32,
+
+    pub rest_mops: u32,
+    pub jops: BigUint,
+
+    pub host_public_inputs: u32,
+    pub context_in_index: u32,
+    pub context_out_index: u32,
+    pub external_host_call_call_index: u32,
+}
+
+impl From<&Status<'_>> for StatusRecord {
+    fn from(status: &Status) -> Self {
+        StatusRecord {
+            eid: status.eid,
+            fid: status.fid,
+            iid: status.iid,
+            sp: status.sp,
+            last_jump_eid: status.last_jump_eid,
+            allocated_memory_pages: status.allocated_memory_pages,
+
+            rest_mops: status.rest_mops,
+            jops: status.jops.clone(),
+
+            host_public_inputs: status.host_public_inputs,
+            context_in_index: status.context_in_index,
+            context_out_index: status.context_out_index,
+            external_host_call_call_index: status.external_host_call_call_index,
+        }
+    }
+}
+
+impl StatusRecord {
+    const CSV_HEADER: [&'static str; 12] = [
+        "eid",
+        "fid",
+        "iid",
+        "sp",
+        "last_jump_eid",
+        "allocated_memory_pages",
+        "rest_mops",
+        "jops",
+        "host_public_inputs",
+        "context_in_index",
+        "context_out_index",
+        "external_host_call_call_index",
+    ];
+
+    // Values in the order of `CSV_HEADER`.
+    fn to_csv_row(&self) -> [String; 12] {
+        [
+            self.eid.to_string(),
+            self.fid.to_string(),
+            self.iid.to_string(),
+            self.sp.to_string(),
+            self.last_jump_eid.to_string(),
+            self.allocated_memory_pages.to_string(),
+            self.rest_mops.to_string(),
+            self.jops.to_string(),
+            self.host_public_inputs.to_string(),
+            self.context_in_index.to_string(),
+            self.context_out_index.to_string(),
+            self.external_host_call_call_index.to_string(),
+        ]
+    }
+
+    /// Reattach the instruction table the status refers to.
+    pub fn into_status(self, itable: &InstructionTable) -> Status<'_> {
+        Status {
+            eid: self.eid,
+            fid: self.fid,
+            iid: self.iid,
+            sp: self.sp,
+            last_jump_eid: self.last_jump_eid,
+            allocated_memory_pages: self.allocated_memory_pages,
+
+            rest_mops: self.rest_mops,
+            jops: self.jops,
+
+            host_public_inputs: self.host_public_inputs,
+            context_in_index: self.context_in_index,
+            context_out_index: self.context_out_index,
+            external_host_call_call_index: self.external_host_call_call_index,
+
+            itable,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SavedStatuses<'a, 'b> {
+    itable_digest: [u8; 32],
+    statuses: &'a [Status<'b>],
+}
+
+#[derive(Deserialize)]
+struct LoadedStatuses {
+    itable_digest: [u8; 32],
+    statuses: Vec<StatusRecord>,
+}
+
+/// Persist a status sequence, e.g. the one returned by `EventTableChip::status_sequence`. The
+/// instruction table is recorded by its digest only.
+pub fn save_statuses(
+    path: &Path,
+    itable: &InstructionTable,
+    statuses: &[Status],
+) -> std::io::Result<()> {
+    let saved = SavedStatuses {
+        itable_digest: itable_digest(itable),
+        statuses,
+    };
+
+    std::fs::write(path, serde_json::to_string(&saved)?)
+}
+
+/// Load a status sequence saved by `save_statuses`, `itable` must be the one it was saved with.
+pub fn load_statuses<'a>(
+    path: &Path,
+    itable: &'a InstructionTable,
+) -> std::io::Result<Vec<Status<'a>>> {
+    let loaded: LoadedStatuses = serde_json::from_slice(&std::fs::read(path)?)?;
+
+    if loaded.itable_digest != itable_digest(itable) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "the statuses were saved with another instruction table",
+        ));
+    }
+
+    Ok(loaded
+        .statuses
+        .into_iter()
+        .map(|record| record.into_status(itable))
+        .collect())
+}
+
+/// A step recorded by an independent interpreter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReferenceStep {
+    pub eid: u32,
+    pub sp: u32,
+    pub fid: u32,
+    pub iid: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DivergenceReport {
+    Length {
+        steps: usize,
+        reference_steps: usize,
+    },
+    Field {
+        eid: u32,
+        field: &'static str,
+        status: u32,
+        reference: u32,
+    },
+}
+
+/// Compare the status of each step (the terminal status is excluded) against a reference trace,
+/// returns the first divergence.
+pub fn verify_against_reference(
+    status: &[Status],
+    reference: &[ReferenceStep],
+) -> Result<(), DivergenceReport> {
+    let steps = status.len().saturating_sub(1);
+
+    for (status, reference) in status[..steps].iter().zip(reference.iter()) {
+        for (field, status_value, reference_value) in [
+            ("eid", status.eid, reference.eid),
+            ("sp", status.sp, reference.sp),
+            ("fid", status.fid, reference.fid),
+            ("iid", status.iid, reference.iid),
+        ] {
+            if status_value != reference_value {
+                return Err(DivergenceReport::Field {
+                    eid: status.eid,
+                    field,
+                    status: status_value,
+                    reference: reference_value,
+                });
+            }
+        }
+    }
+
+    if steps != reference.len() {
+        return Err(DivergenceReport::Length {
+            steps,
+            reference_steps: reference.len(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Length of the common prefix of two status sequences, i.e. the number of leading steps which
+/// would be assigned identically. Since rest_mops and jops (non-continuation) count down to the
+/// end of the slice, any difference in the tail affects them as well.
+pub fn common_prefix_len(prev: &[Status], new: &[Status]) -> usize {
+    prev.iter()
+        .zip(new.iter())
+        .take_while(|(prev, new)| StatusRecord::from(*prev) == StatusRecord::from(*new))
+        .count()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConsistencyError {
+    // The resolved lifespan of a read doesn't cover the eid of the step.
+    UncoveredRead {
+        eid: u32,
+        ltype: LocationType,
+        offset: u32,
+        start_eid: u32,
+        end_eid: u32,
+    },
+}
+
+/// Check that every read of a step lies in its resolved lifespan, i.e.
+/// `start_eid <= status.eid <= end_eid`.
+pub fn validate_status_memory_consistency(
+    event_table: &EventTableWithMemoryInfo,
+    status: &[Status],
+) -> Result<(), ConsistencyError> {
+    for (entry, current, _) in event_table.iter_with_status(status) {
+        for rw in entry.memory_effects() {
+            if rw.entry.atype == AccessType::Read
+                && !(rw.start_eid <= current.eid && current.eid <= rw.end_eid)
+            {
+                return Err(ConsistencyError::UncoveredRead {
+                    eid: current.eid,
+                    ltype: rw.entry.ltype,
+                    offset: rw.entry.offset,
+                    start_eid: rw.start_eid,
+                    end_eid: rw.end_eid,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
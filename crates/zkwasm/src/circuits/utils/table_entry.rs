@@ -1,25 +1,60 @@
+use halo2_proofs::arithmetic::FieldExt;
+use num_bigint::BigInt;
+use num_bigint::BigUint;
+use rayon::iter::IndexedParallelIterator;
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::IntoParallelRefIterator;
+use rayon::iter::ParallelIterator;
+use serde::Deserialize;
 use serde::Serialize;
+use sha2::digest::Output;
+use sha2::Digest;
+use specs::configure_table::ConfigureTable;
 use specs::etable::EventTable;
 use specs::etable::EventTableEntry;
+use specs::itable::InstructionTable;
+use specs::itable::OpcodeClassPlain;
 use specs::mtable::AccessType;
 use specs::mtable::LocationType;
 use specs::mtable::MTable;
 use specs::mtable::MemoryTableEntry;
-use std::cmp::Ordering;
+use specs::step::StepInfo;
+use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::collections::HashSet;
-use std::env;
 use std::io::Write;
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::Path;
 
 use crate::circuits::config::common_range_max;
+use crate::circuits::image_table::PAGE_ENTRIES;
+use crate::circuits::mtable::MEMORY_TABLE_ENTRY_ROWS;
+use crate::circuits::utils::field_to_bn;
+use crate::circuits::utils::interval_index::IntervalIndex;
+use crate::circuits::utils::step_status::Status;
+use crate::error::BuildError;
+use crate::error::MTableError;
 use crate::runtime::memory_event_of_step;
 
-#[derive(Clone, Debug, Serialize)]
-pub(in crate::circuits) struct MemoryWritingEntry {
+/// Stable ordering key of a location, independent of the derived `Ord` of `LocationType`.
+/// Stack, Heap and Global are mapped to 1, 2 and 3 respectively.
+pub fn location_sort_key(ltype: LocationType, offset: u32) -> (u8, u32) {
+    let ltype = match ltype {
+        LocationType::Stack => 1,
+        LocationType::Heap => 2,
+        LocationType::Global => 3,
+    };
+
+    (ltype, offset)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MemoryWritingEntry {
     index: usize,
-    pub(in crate::circuits) entry: MemoryTableEntry,
-    pub(in crate::circuits) end_eid: u32,
+    pub entry: MemoryTableEntry,
+    pub end_eid: u32,
 }
 
 impl MemoryWritingEntry {
@@ -28,14 +63,100 @@ impl MemoryWritingEntry {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundaryMismatch {
+    pub ltype: LocationType,
+    pub offset: u32,
+    // value finalized by the previous slice
+    pub finalized_value: u64,
+    // value initialized by the next slice
+    pub initial_value: u64,
+}
+
+/// How to resolve a read of a global which no write covers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReadPolicy {
+    /// Reject it as `BuildError::UncoveredRead`(or `UnknownLocation`), like `new` does.
+    Strict,
+    /// Read the zero a WASM global is initialized to.
+    #[default]
+    Lenient,
+}
+
+/// An entry whose value doesn't fit the field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OversizedValue {
+    pub eid: u32,
+    pub ltype: LocationType,
+    pub offset: u32,
+    pub value: u64,
+}
+
+/// A heap write beyond the maximal memory pages of the configure table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutOfBoundsWrite {
+    pub eid: u32,
+    // offset of the u64 entry
+    pub offset: u32,
+    // exclusive bound of the offset
+    pub bound: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MemoryWritingTable(pub(in crate::circuits) Vec<MemoryWritingEntry>);
 
 impl MemoryWritingTable {
+    // The entries finalizing the memory, i.e. the last write of each written location.
+    fn finalizing_entries(&self) -> impl Iterator<Item = &MemoryWritingEntry> {
+        self.0.iter().enumerate().filter_map(|(index, entry)| {
+            (entry.entry.atype == AccessType::Write
+                && self.0.get(index + 1).map_or(true, |next_entry| {
+                    !next_entry.entry.is_same_location(&entry.entry)
+                }))
+            .then_some(entry)
+        })
+    }
+
     pub(crate) fn count_rest_memory_finalize_ops(&self) -> (u32, HashSet<(LocationType, u32)>) {
         let mut count = 0u32;
         let mut set = HashSet::default();
 
+        for entry in self.finalizing_entries() {
+            set.insert((entry.entry.ltype, entry.entry.offset));
+            count += 1;
+        }
+
+        (count, set)
+    }
+
+    /// Same count as `count_rest_memory_finalize_ops` without collecting the locations.
+    pub fn finalize_op_count(&self) -> u32 {
+        self.finalizing_entries().count() as u32
+    }
+
+    /// Predicts the number of rows occupied by the memory table. A finalized write keeps its
+    /// post image on its own entry rows, so finalization contributes no extra rows.
+    pub fn predicted_rows(&self) -> usize {
+        self.0.len() * MEMORY_TABLE_ENTRY_ROWS as usize
+    }
+
+    /// Approximate heap usage of the table, based on the allocated capacity.
+    pub fn approx_heap_bytes(&self) -> usize {
+        std::mem::size_of::<Self>() + self.0.capacity() * std::mem::size_of::<MemoryWritingEntry>()
+    }
+
+    /// Compares the locations finalized by this slice with the initial memory of the next slice.
+    /// Locations not touched by the next slice are skipped.
+    pub fn boundary_diff(&self, next: &MemoryWritingTable) -> Vec<BoundaryMismatch> {
+        let initial_values = next
+            .0
+            .iter()
+            .filter(|entry| entry.entry.atype == AccessType::Init)
+            .map(|entry| ((entry.entry.ltype, entry.entry.offset), entry.entry.value))
+            .collect::<HashMap<_, _>>();
+
+        let mut mismatches = vec![];
+
         let mut iter = self.0.iter().peekable();
 
         while let Some(entry) = iter.next() {
@@ -44,20 +165,427 @@ impl MemoryWritingTable {
                     !next_entry.entry.is_same_location(&entry.entry)
                 })
             {
-                set.insert((entry.entry.ltype, entry.entry.offset));
-                count += 1;
+                if let Some(initial_value) =
+                    initial_values.get(&(entry.entry.ltype, entry.entry.offset))
+                {
+                    if *initial_value != entry.entry.value {
+                        mismatches.push(BoundaryMismatch {
+                            ltype: entry.entry.ltype,
+                            offset: entry.entry.offset,
+                            finalized_value: entry.entry.value,
+                            initial_value: *initial_value,
+                        });
+                    }
+                }
             }
         }
 
-        (count, set)
+        mismatches
+    }
+
+    /// Returns the eid of the last write at the location, i.e. the one whose lifespan extends to
+    /// the maximal eid. None if the location is never written.
+    pub fn finalizing_event(&self, ltype: LocationType, offset: u32) -> Option<u32> {
+        let end = self
+            .0
+            .partition_point(|entry| (entry.entry.ltype, entry.entry.offset) <= (ltype, offset));
+
+        self.0[..end]
+            .last()
+            .filter(|entry| {
+                entry.entry.ltype == ltype
+                    && entry.entry.offset == offset
+                    && entry.entry.atype == AccessType::Write
+            })
+            .map(|entry| entry.entry.eid)
+    }
+
+    /// Whether a read at `eid` is covered by the lifespan of some entry at the location. Build an
+    /// `IntervalIndex` once instead for repeated queries.
+    pub fn covers_read(&self, ltype: LocationType, offset: u32, eid: u32) -> bool {
+        IntervalIndex::new(self)
+            .query_point(ltype, offset, eid)
+            .is_some()
+    }
+
+    /// Writes whose lifespan `(eid, end_eid]` contains `boundary_eid`, i.e. the ones carrying
+    /// state across a slice boundary at that eid. Ordered as the table.
+    pub fn entries_crossing(&self, boundary_eid: u32) -> Vec<&MemoryWritingEntry> {
+        let index = IntervalIndex::new(self);
+
+        let mut entries = index
+            .locations()
+            .filter_map(|(ltype, offset)| index.query_point(*ltype, *offset, boundary_eid))
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.index);
+
+        entries
+    }
+
+    /// Contiguous runs of entries at the same location, in table order. Fails if a location
+    /// reappears after another one, i.e. the table is not grouped by location.
+    pub fn group_by_location(
+        &self,
+    ) -> Result<impl Iterator<Item = ((LocationType, u32), &[MemoryWritingEntry])>, MTableError>
+    {
+        // Grouped by location as long as the locations never decrease.
+        if let Some(pair) = self.0.windows(2).find(|pair| {
+            location_sort_key(pair[1].entry.ltype, pair[1].entry.offset)
+                < location_sort_key(pair[0].entry.ltype, pair[0].entry.offset)
+        }) {
+            return Err(MTableError::UngroupedLocation {
+                ltype: pair[1].entry.ltype,
+                offset: pair[1].entry.offset,
+            });
+        }
+
+        let mut rest = &self.0[..];
+
+        Ok(std::iter::from_fn(move || {
+            let first = rest.first()?;
+            let location = (first.entry.ltype, first.entry.offset);
+
+            let len = rest
+                .iter()
+                .position(|entry| (entry.entry.ltype, entry.entry.offset) != location)
+                .unwrap_or(rest.len());
+            let (group, remaining) = rest.split_at(len);
+            rest = remaining;
+
+            Some((location, group))
+        }))
+    }
+
+    /// Check that every heap write falls within the maximal memory pages of `configure_table`.
+    pub fn validate_against_config(
+        &self,
+        configure_table: &ConfigureTable,
+    ) -> Result<(), Vec<OutOfBoundsWrite>> {
+        let bound = configure_table.maximal_memory_pages as u64 * PAGE_ENTRIES as u64;
+
+        let violations = self
+            .0
+            .iter()
+            .filter(|entry| {
+                entry.entry.ltype == LocationType::Heap
+                    && entry.entry.atype == AccessType::Write
+                    && entry.entry.offset as u64 >= bound
+            })
+            .map(|entry| OutOfBoundsWrite {
+                eid: entry.entry.eid,
+                offset: entry.entry.offset,
+                bound,
+            })
+            .collect::<Vec<_>>();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Check that the value of every entry is below the modulus of `F`, i.e. converts to a field
+    /// element without reduction. Collects all the oversized values.
+    pub fn validate_values_fit_field<F: FieldExt>(&self) -> Result<(), Vec<OversizedValue>> {
+        let max = field_to_bn(&-F::one());
+
+        let oversized = self
+            .0
+            .iter()
+            .filter(|entry| BigUint::from(entry.entry.value) > max)
+            .map(|entry| OversizedValue {
+                eid: entry.entry.eid,
+                ltype: entry.entry.ltype,
+                offset: entry.entry.offset,
+                value: entry.entry.value,
+            })
+            .collect::<Vec<_>>();
+
+        if oversized.is_empty() {
+            Ok(())
+        } else {
+            Err(oversized)
+        }
+    }
+
+    /// The `n` locations written by the most steps with their number of writes, in descending
+    /// order. Ties are kept in table order.
+    pub fn hottest_locations(
+        &self,
+        n: usize,
+    ) -> Result<Vec<((LocationType, u32), usize)>, MTableError> {
+        let mut locations = self
+            .group_by_location()?
+            .map(|(location, entries)| {
+                let writes = entries
+                    .iter()
+                    .filter(|entry| entry.entry.atype == AccessType::Write)
+                    .count();
+
+                (location, writes)
+            })
+            .collect::<Vec<_>>();
+
+        locations.sort_by(|(_, a), (_, b)| b.cmp(a));
+        locations.truncate(n);
+
+        Ok(locations)
+    }
+
+    /// Number of writes per bucket, splitting the eids `[0, total_eids)` into `buckets` equal
+    /// ranges. Writes at or beyond `total_eids` are counted in the last bucket.
+    pub fn write_density(&self, buckets: usize, total_eids: u32) -> Vec<u32> {
+        let mut density = vec![0u32; buckets];
+
+        if buckets == 0 {
+            return density;
+        }
+
+        for entry in &self.0 {
+            if entry.entry.atype == AccessType::Write {
+                let bucket =
+                    entry.entry.eid as u64 * buckets as u64 / u64::max(total_eids as u64, 1);
+
+                density[usize::min(bucket as usize, buckets - 1)] += 1;
+            }
+        }
+
+        density
+    }
+
+    /// The eid at which most lifespans `[eid, end_eid)` overlap, along with their number. (0, 0)
+    /// if the table is empty.
+    pub fn max_concurrent_live(&self) -> (u32, usize) {
+        let mut events = self
+            .0
+            .iter()
+            .flat_map(|entry| [(entry.entry.eid, 1i64), (entry.end_eid, -1i64)])
+            .collect::<Vec<_>>();
+
+        // Lifespans are half open, so a closing one is not live at the eid it ends.
+        events.sort();
+
+        let mut live = 0i64;
+        let mut max = (0, 0);
+
+        for (eid, delta) in events {
+            live += delta;
+
+            if live as usize > max.1 {
+                max = (eid, live as usize);
+            }
+        }
+
+        max
+    }
+
+    /// Running hash over the entries in table order. Each entry is encoded as
+    /// `ltype(u8) || offset(u32) || eid(u32) || end_eid(u32) || atype(u8) || vtype(u8) ||
+    /// is_mutable(u8) || value(u64)`, integers in little endian, ltype as `location_sort_key`,
+    /// atype and vtype as their discriminants.
+    pub fn commitment<H: Digest>(&self) -> Output<H> {
+        let mut hasher = H::new();
+
+        for entry in &self.0 {
+            let (ltype, offset) = location_sort_key(entry.entry.ltype, entry.entry.offset);
+
+            hasher.update([ltype]);
+            hasher.update(offset.to_le_bytes());
+            hasher.update(entry.entry.eid.to_le_bytes());
+            hasher.update(entry.end_eid.to_le_bytes());
+            hasher.update([
+                entry.entry.atype as u8,
+                entry.entry.vtype as u8,
+                entry.entry.is_mutable as u8,
+            ]);
+            hasher.update(entry.entry.value.to_le_bytes());
+        }
+
+        hasher.finalize()
+    }
+
+    // Build a table from a subset of entries, keeping their order.
+    fn reindexed(entries: impl Iterator<Item = MemoryWritingEntry>) -> Self {
+        MemoryWritingTable(
+            entries
+                .enumerate()
+                .map(|(index, entry)| MemoryWritingEntry { index, ..entry })
+                .collect(),
+        )
+    }
+
+    /// Coalesce consecutive entries of the same location and access type holding the same value
+    /// over contiguous lifespans into the first of them, spanning the combined lifespan, then
+    /// re-index. An init entry is never merged with a write.
+    ///
+    /// The result drops writes performed by the event table, it's meant for analysis and can't be
+    /// assigned.
+    pub fn canonicalize(&self) -> MemoryWritingTable {
+        let mut entries: Vec<MemoryWritingEntry> = Vec::with_capacity(self.0.len());
+
+        for entry in &self.0 {
+            if let Some(last) = entries.last_mut() {
+                if last.is_same_memory_address(entry)
+                    && last.entry.atype == entry.entry.atype
+                    && last.entry.vtype == entry.entry.vtype
+                    && last.entry.value == entry.entry.value
+                    && last.end_eid == entry.entry.eid
+                {
+                    last.end_eid = entry.end_eid;
+
+                    continue;
+                }
+            }
+
+            entries.push(entry.clone());
+        }
+
+        Self::reindexed(entries.into_iter())
+    }
+
+    /// Partition the table into one table per location type. Since lifespans are chained within
+    /// the same location, each sub table keeps its end_eid.
+    pub fn split_by_location(&self) -> BTreeMap<LocationType, MemoryWritingTable> {
+        let mut partitions = BTreeMap::<_, Vec<_>>::new();
+
+        for entry in &self.0 {
+            partitions
+                .entry(entry.entry.ltype)
+                .or_default()
+                .push(entry.clone());
+        }
+
+        partitions
+            .into_iter()
+            .map(|(ltype, entries)| (ltype, Self::reindexed(entries.into_iter())))
+            .collect()
+    }
+
+    /// Drop the locations holding a single entry, whose lifespan therefore spans to the maximal
+    /// eid. The rest is the memory mutated during the execution.
+    pub fn exclude_constant_locations(&self) -> MemoryWritingTable {
+        let mut occurrences = HashMap::<_, usize>::new();
+
+        for entry in &self.0 {
+            *occurrences
+                .entry((entry.entry.ltype, entry.entry.offset))
+                .or_default() += 1;
+        }
+
+        Self::reindexed(
+            self.0
+                .iter()
+                .filter(|entry| occurrences[&(entry.entry.ltype, entry.entry.offset)] > 1)
+                .cloned(),
+        )
+    }
+
+    /// Same as `from` but rejects duplicate writes at the same eid and location, which would
+    /// otherwise be silently dropped when chaining lifespans.
+    pub fn checked_from(k: u32, value: MTable) -> Result<Self, MTableError> {
+        Self::checked_from_with_common_range(common_range_max(k), value)
+    }
+
+    /// Same as `from_with_common_range` with the duplicate check of `checked_from`. The circuit
+    /// builds its table with it.
+    pub fn checked_from_with_common_range(
+        common_range_max: u32,
+        value: MTable,
+    ) -> Result<Self, MTableError> {
+        let mut writes = value
+            .entries()
+            .iter()
+            .filter(|entry| entry.atype == AccessType::Write)
+            .peekable();
+
+        while let Some(entry) = writes.next() {
+            if let Some(next_entry) = writes.peek() {
+                if next_entry.eid == entry.eid && next_entry.is_same_location(entry) {
+                    return Err(MTableError::DuplicateWrite {
+                        eid: entry.eid,
+                        ltype: entry.ltype,
+                        offset: entry.offset,
+                    });
+                }
+            }
+        }
+
+        Ok(Self::from_with_common_range(common_range_max, value))
     }
 
     pub fn from(k: u32, value: MTable) -> Self {
-        let maximal_eid = if cfg!(feature = "continuation") {
+        Self::from_with_common_range(common_range_max(k), value)
+    }
+
+    fn maximal_eid(common_range_max: u32) -> u32 {
+        if cfg!(feature = "continuation") {
             u32::MAX
         } else {
-            common_range_max(k)
-        };
+            common_range_max
+        }
+    }
+
+    /// Build the table with an explicit common range maximum instead of deriving it from k. It
+    /// only takes effect on the maximal eid in non-continuation mode.
+    ///
+    /// Lifespans are chained per location, so the locations are processed in parallel. The result
+    /// is identical to `from_with_common_range_sequential`.
+    pub fn from_with_common_range(common_range_max: u32, value: MTable) -> Self {
+        let maximal_eid = Self::maximal_eid(common_range_max);
+
+        let mut entries: Vec<MemoryWritingEntry> = value
+            .entries()
+            .par_iter()
+            .filter(|entry| entry.atype != AccessType::Read)
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .enumerate()
+            .map(|(index, entry)| MemoryWritingEntry {
+                index,
+                entry: entry.clone(),
+                end_eid: maximal_eid,
+            })
+            .collect();
+
+        // The memory table is ordered by location, split it into a group per location.
+        let mut groups = vec![];
+        let mut rest = &mut entries[..];
+        while !rest.is_empty() {
+            let len = rest
+                .iter()
+                .position(|entry| !entry.is_same_memory_address(&rest[0]))
+                .unwrap_or(rest.len());
+            let (group, remaining) = std::mem::take(&mut rest).split_at_mut(len);
+
+            groups.push(group);
+            rest = remaining;
+        }
+
+        groups.into_par_iter().for_each(|group| {
+            let mut next_eid = None;
+
+            for entry in group.iter_mut().rev() {
+                if let Some(eid) = next_eid {
+                    entry.end_eid = eid;
+                }
+
+                next_eid = Some(entry.entry.eid);
+            }
+        });
+
+        // Drop the entries with an empty lifespan, as the sequential builder does.
+        let entries = entries
+            .into_par_iter()
+            .filter(|entry| entry.entry.eid != entry.end_eid)
+            .collect();
+
+        MemoryWritingTable(entries)
+    }
+
+    /// The sequential reference of `from_with_common_range`.
+    pub fn from_with_common_range_sequential(common_range_max: u32, value: MTable) -> Self {
+        let maximal_eid = Self::maximal_eid(common_range_max);
         let mut index = 0;
 
         let mut entries: Vec<MemoryWritingEntry> = value
@@ -100,92 +628,360 @@ impl MemoryWritingTable {
 }
 
 impl MemoryWritingTable {
-    // (location, offset) |-> Vec<(start_eid, end_eid)>
-    fn build_lookup_mapping(&self) -> BTreeMap<(LocationType, u32), Vec<(u32, u32)>> {
-        let mut mapping = BTreeMap::<_, Vec<(u32, u32)>>::new();
+    fn write_file(dir: &Path, filename: &str, buf: &str) {
+        let mut fd = std::fs::File::create(dir.join(filename)).unwrap();
 
-        for entry in &self.0 {
-            let ltype = entry.entry.ltype;
-            let offset = entry.entry.offset;
-            let start_eid = entry.entry.eid;
-            let end_eid = entry.end_eid;
+        fd.write_all(buf.as_bytes()).unwrap();
+    }
 
-            if let Some(entries) = mapping.get_mut(&(ltype, offset)) {
-                entries.push((start_eid, end_eid));
-            } else {
-                mapping.insert((ltype, offset), vec![(start_eid, end_eid)]);
-            }
-        }
+    pub fn write_json(&self, dir: &Path) {
+        let mtable = serde_json::to_string_pretty(self).unwrap();
 
-        mapping
+        Self::write_file(dir, "memory_writing_table.json", &mtable);
     }
 
-    pub fn write_json(&self, dir: Option<PathBuf>) {
-        fn write_file(folder: &PathBuf, filename: &str, buf: &String) {
-            let mut folder = folder.clone();
-            folder.push(filename);
-            let mut fd = std::fs::File::create(folder.as_path()).unwrap();
-            folder.pop();
+    /// Same schema as `write_json`, without the pretty-printing whitespace.
+    pub fn write_json_compact(&self, dir: &Path) {
+        let mtable = serde_json::to_string(self).unwrap();
 
-            fd.write(buf.as_bytes()).unwrap();
-        }
+        Self::write_file(dir, "memory_writing_table.json", &mtable);
+    }
 
-        let mtable = serde_json::to_string_pretty(self).unwrap();
+    /// Dump the entries whose lifespan `(eid, end_eid]` overlaps `(start_eid, end_eid]`,
+    /// reindexed as a standalone table.
+    pub fn write_json_eid_range(&self, dir: &Path, start_eid: u32, end_eid: u32) {
+        let index = IntervalIndex::new(self);
+
+        let mut entries = index
+            .locations()
+            .flat_map(|(ltype, offset)| index.query_range(*ltype, *offset, start_eid, end_eid))
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.index);
+
+        let table = Self::reindexed(entries.into_iter().cloned());
+
+        let mtable = serde_json::to_string_pretty(&table).unwrap();
+
+        Self::write_file(
+            dir,
+            &format!("memory_writing_table.{}-{}.json", start_eid, end_eid),
+            &mtable,
+        );
+    }
 
-        let dir = dir.unwrap_or(env::current_dir().unwrap());
-        write_file(&dir, "memory_writing_table.json", &mtable);
+    /// Entries in execution order, the table itself stays ordered by location.
+    pub fn sorted_by_eid(&self) -> Vec<&MemoryWritingEntry> {
+        let mut entries = self.0.iter().collect::<Vec<_>>();
+
+        entries.sort_by_key(|entry| {
+            (
+                entry.entry.eid,
+                location_sort_key(entry.entry.ltype, entry.entry.offset),
+            )
+        });
+
+        entries
+    }
+
+    pub fn write_json_by_eid(&self, dir: &Path) {
+        let mtable = serde_json::to_string_pretty(&self.sorted_by_eid()).unwrap();
+
+        Self::write_file(dir, "memory_writing_table_by_eid.json", &mtable);
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MemoryRWEntry {
     pub entry: MemoryTableEntry,
     pub start_eid: u32,
     pub end_eid: u32,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct EventTableEntryWithMemoryInfo {
     pub eentry: EventTableEntry,
     pub memory_rw_entires: Vec<MemoryRWEntry>,
 }
 
-#[derive(Debug)]
-pub(crate) struct EventTableWithMemoryInfo(
-    pub(in crate::circuits) Vec<EventTableEntryWithMemoryInfo>,
-);
+impl EventTableEntryWithMemoryInfo {
+    /// Memory reads and writes of the step with their resolved lifespans.
+    pub fn memory_effects(&self) -> &[MemoryRWEntry] {
+        &self.memory_rw_entires
+    }
 
-impl EventTableWithMemoryInfo {
-    pub(in crate::circuits) fn new(
-        event_table: &EventTable,
-        memory_writing_table: &MemoryWritingTable,
-    ) -> Self {
-        let lookup = memory_writing_table.build_lookup_mapping();
+    /// Human readable rendering of the step and its memory effects, for debugging.
+    pub fn describe(&self, itable: &InstructionTable) -> String {
+        let instruction = self.eentry.get_instruction(itable);
 
-        let lookup_mtable_eid = |(eid, ltype, offset, is_writing)| {
-            let records = lookup.get(&(ltype, offset)).unwrap();
+        let mut lines = vec![format!(
+            "eid: {}, fid: {}, iid: {}, sp: {}, opcode: {:?}",
+            self.eentry.eid, self.eentry.fid, self.eentry.iid, self.eentry.sp, instruction.opcode
+        )];
 
-            if is_writing {
-                let idx = records
-                    .binary_search_by(|(start_eid, _)| start_eid.cmp(eid))
-                    .unwrap();
-                records[idx]
-            } else {
-                let idx = records
-                    .binary_search_by(|(start_eid, end_eid)| {
-                        if eid <= start_eid {
-                            Ordering::Greater
-                        } else if eid > end_eid {
-                            Ordering::Less
-                        } else {
-                            Ordering::Equal
-                        }
+        lines.extend(self.memory_rw_entires.iter().map(|rw| {
+            format!(
+                "  {:?} {:?}[{}] = {} ({:?}), lifespan: [{}, {})",
+                rw.entry.atype,
+                rw.entry.ltype,
+                rw.entry.offset,
+                rw.entry.value,
+                rw.entry.vtype,
+                rw.start_eid,
+                rw.end_eid
+            )
+        }));
+
+        lines.join("\n")
+    }
+}
+
+fn resolve_memory_rw_entries(
+    lookup: &IntervalIndex,
+    eentry: &EventTableEntry,
+) -> Vec<MemoryRWEntry> {
+    try_resolve_memory_rw_entries(lookup, eentry).unwrap()
+}
+
+fn try_resolve_memory_rw_entries(
+    lookup: &IntervalIndex,
+    eentry: &EventTableEntry,
+) -> Result<Vec<MemoryRWEntry>, BuildError> {
+    memory_event_of_step(eentry)
+        .iter()
+        .map(|mentry| resolve_memory_rw_entry(lookup, eentry.eid, mentry))
+        .collect()
+}
+
+fn resolve_memory_rw_entry(
+    lookup: &IntervalIndex,
+    eid: u32,
+    mentry: &MemoryTableEntry,
+) -> Result<MemoryRWEntry, BuildError> {
+    let ltype = mentry.ltype;
+    let offset = mentry.offset;
+
+    if !lookup.contains_location(ltype, offset) {
+        return Err(BuildError::UnknownLocation { eid, ltype, offset });
+    }
+
+    let record = if mentry.atype == AccessType::Write {
+        lookup
+            .query_write(ltype, offset, eid)
+            .ok_or(BuildError::MissingWrite { eid, ltype, offset })?
+    } else {
+        lookup
+            .query_point(ltype, offset, eid)
+            .ok_or(BuildError::UncoveredRead { eid, ltype, offset })?
+    };
+
+    let start_eid = record.entry.eid;
+    let end_eid = record.end_eid;
+
+    Ok(MemoryRWEntry {
+        entry: mentry.clone(),
+        start_eid,
+        end_eid,
+    })
+}
+
+/// Locations read by some step which observe no value written ahead, i.e. the read is covered by
+/// no entry, or by an `Init` entry of zero which the image leaves to the implicit zero
+/// initialization. Ordered as the memory table.
+pub fn locations_read_but_never_written(
+    event_table: &EventTable,
+    mtable: &MemoryWritingTable,
+) -> Vec<(LocationType, u32)> {
+    let index = IntervalIndex::new(mtable);
+
+    event_table
+        .entries()
+        .iter()
+        .flat_map(memory_event_of_step)
+        .filter(|entry| {
+            entry.atype == AccessType::Read
+                && index
+                    .query_point(entry.ltype, entry.offset, entry.eid)
+                    .map_or(true, |covering| {
+                        covering.entry.atype == AccessType::Init && covering.entry.value == 0
                     })
-                    .unwrap();
+        })
+        .map(|entry| (entry.ltype, entry.offset))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
 
-                records[idx]
-            }
-        };
+// Memory events of a sub-range of a trace, along with the init entries of the locations they
+// access, holding the values observed by the first step of the range in `index` of the whole trace.
+fn sub_trace_memory_table(event_table: &EventTable, index: &IntervalIndex) -> MTable {
+    let first_eid = event_table.entries().first().map_or(0, |entry| entry.eid);
+
+    let mut memory_entries = event_table
+        .entries()
+        .iter()
+        .flat_map(memory_event_of_step)
+        .collect::<Vec<_>>();
+
+    let locations = memory_entries
+        .iter()
+        .map(|entry| (entry.ltype, entry.offset))
+        .collect::<BTreeSet<_>>();
+
+    memory_entries.extend(locations.into_iter().filter_map(|(ltype, offset)| {
+        index
+            .query_point(ltype, offset, first_eid)
+            .map(|covering| MemoryTableEntry {
+                eid: 0,
+                atype: AccessType::Init,
+                ..covering.entry.clone()
+            })
+    }));
+
+    memory_entries.sort_by_key(|entry| (entry.ltype, entry.offset, entry.eid));
+
+    MTable::from(memory_entries)
+}
+
+/// Rough estimate of the peak memory held by the event table assignment, in bytes. It sums the
+/// dominant allocations only: the status sequence, the entries along with their memory rw
+/// entries, the memory writing table and its lookup mapping. Heap data owned by the entries, e.g.
+/// the values of a `StepInfo`, and the memory of the prover itself are not counted.
+pub fn estimate_peak_bytes(
+    event_table: &EventTableWithMemoryInfo,
+    mtable: &MemoryWritingTable,
+) -> usize {
+    let entries = event_table.0.len();
+    let rw_entries = event_table
+        .0
+        .iter()
+        .map(|entry| entry.memory_rw_entires.len())
+        .sum::<usize>();
+
+    // one status per entry and the terminal one
+    (entries + 1) * std::mem::size_of::<Status>()
+        + entries * std::mem::size_of::<EventTableEntryWithMemoryInfo>()
+        + rw_entries * std::mem::size_of::<MemoryRWEntry>()
+        + mtable.approx_heap_bytes()
+        + mtable.0.len() * std::mem::size_of::<(u32, u32)>()
+}
+
+/// Memory writing ops and jops per opcode class.
+pub type CostBreakdown = BTreeMap<OpcodeClassPlain, (u32, BigUint)>;
+
+/// Mops and jops deltas from `a` to `b` per class, ordered by class. A class absent from one side
+/// counts as zero there.
+pub fn diff_breakdown(
+    a: &CostBreakdown,
+    b: &CostBreakdown,
+) -> Vec<(OpcodeClassPlain, i64, BigInt)> {
+    let classes = a.keys().chain(b.keys()).collect::<BTreeSet<_>>();
+
+    classes
+        .into_iter()
+        .map(|class| {
+            let (mops_a, jops_a) = a.get(class).cloned().unwrap_or_default();
+            let (mops_b, jops_b) = b.get(class).cloned().unwrap_or_default();
+
+            (
+                *class,
+                mops_b as i64 - mops_a as i64,
+                BigInt::from(jops_b) - BigInt::from(jops_a),
+            )
+        })
+        .collect()
+}
+
+/// A step whose instruction is absent from the instruction table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingInstruction {
+    pub eid: u32,
+    pub fid: u32,
+    pub iid: u32,
+}
+
+/// A step whose stack pointer is out of bounds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpViolation {
+    pub eid: u32,
+    pub sp: u32,
+}
+
+/// Location in the guest source, e.g. resolved from its DWARF line table.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLoc {
+    pub file: String,
+    pub line: u32,
+}
+
+/// A function call within an event table, `entry_range` spans the entries of the frame along with
+/// the ones of its callees.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallFrame {
+    // eid of the call entering the frame, i.e. last_jump_eid of its entries
+    pub frame_id: u32,
+    pub fid: u32,
+    pub entry_range: Range<usize>,
+    pub children: Vec<CallFrame>,
+}
+
+/// Render call frames, e.g. those of `EventTableWithMemoryInfo::call_frames`, as a GraphViz DOT
+/// digraph. A node is a function labeled with the number of entries executed in it, excluding its
+/// callees, and an edge is labeled with the number of calls from the caller to the callee.
+pub fn call_frames_to_dot(frames: &[CallFrame]) -> String {
+    fn visit(
+        frame: &CallFrame,
+        instructions: &mut BTreeMap<u32, usize>,
+        calls: &mut BTreeMap<(u32, u32), usize>,
+    ) {
+        let callee_entries = frame
+            .children
+            .iter()
+            .map(|child| child.entry_range.len())
+            .sum::<usize>();
+
+        *instructions.entry(frame.fid).or_default() += frame.entry_range.len() - callee_entries;
+
+        for child in &frame.children {
+            *calls.entry((frame.fid, child.fid)).or_default() += 1;
+
+            visit(child, instructions, calls);
+        }
+    }
+
+    let mut instructions = BTreeMap::new();
+    let mut calls = BTreeMap::new();
+
+    for frame in frames {
+        visit(frame, &mut instructions, &mut calls);
+    }
+
+    let mut lines = vec!["digraph calls {".to_string()];
+
+    lines.extend(instructions.iter().map(|(fid, count)| {
+        format!(
+            "    f{} [label=\"fid {}\\n{} instructions\"];",
+            fid, fid, count
+        )
+    }));
+    lines.extend(calls.iter().map(|((caller, callee), count)| {
+        format!("    f{} -> f{} [label=\"{}\"];", caller, callee, count)
+    }));
+    lines.push("}".to_string());
+
+    lines.join("\n")
+}
+
+#[derive(Debug)]
+pub struct EventTableWithMemoryInfo(pub(in crate::circuits) Vec<EventTableEntryWithMemoryInfo>);
+
+impl EventTableWithMemoryInfo {
+    /// The event table of a circuit without witness, used by key generation.
+    pub fn empty() -> Self {
+        EventTableWithMemoryInfo(vec![])
+    }
+
+    pub(crate) fn new(event_table: &EventTable, memory_writing_table: &MemoryWritingTable) -> Self {
+        let lookup = IntervalIndex::new(memory_writing_table);
 
         EventTableWithMemoryInfo(
             event_table
@@ -193,25 +989,492 @@ impl EventTableWithMemoryInfo {
                 .iter()
                 .map(|eentry| EventTableEntryWithMemoryInfo {
                     eentry: eentry.clone(),
-                    memory_rw_entires: memory_event_of_step(eentry)
-                        .iter()
-                        .map(|mentry| {
-                            let (start_eid, end_eid) = lookup_mtable_eid((
-                                &eentry.eid,
-                                mentry.ltype,
-                                mentry.offset,
-                                mentry.atype == AccessType::Write,
-                            ));
-
-                            MemoryRWEntry {
-                                entry: mentry.clone(),
-                                start_eid,
-                                end_eid,
-                            }
-                        })
-                        .collect(),
+                    memory_rw_entires: resolve_memory_rw_entries(&lookup, eentry),
                 })
                 .collect(),
         )
     }
+
+    /// Same as `new` with `policy` deciding on reads of globals which no write covers. A lenient
+    /// read observes the zero a global defaults to, lasting until the first write of it, see
+    /// `ResolvedEventTable` for getting an assignable table out of the result.
+    pub fn with_read_policy(
+        event_table: &EventTable,
+        memory_writing_table: &MemoryWritingTable,
+        policy: ReadPolicy,
+    ) -> Result<ResolvedEventTable, BuildError> {
+        let lookup = IntervalIndex::new(memory_writing_table);
+        let mut synthetic_writes = BTreeMap::new();
+
+        let entries = event_table
+            .entries()
+            .iter()
+            .map(|eentry| {
+                let memory_rw_entires = memory_event_of_step(eentry)
+                    .iter()
+                    .map(
+                        |mentry| match resolve_memory_rw_entry(&lookup, eentry.eid, mentry) {
+                            Err(
+                                BuildError::UnknownLocation { .. }
+                                | BuildError::UncoveredRead { .. },
+                            ) if policy == ReadPolicy::Lenient
+                                && mentry.ltype == LocationType::Global
+                                && mentry.atype == AccessType::Read =>
+                            {
+                                synthetic_writes.entry(mentry.offset).or_insert_with(|| {
+                                    MemoryTableEntry {
+                                        eid: 0,
+                                        atype: AccessType::Write,
+                                        value: 0,
+                                        ..mentry.clone()
+                                    }
+                                });
+
+                                let end_eid = lookup
+                                    .query_range(mentry.ltype, mentry.offset, eentry.eid, u32::MAX)
+                                    .iter()
+                                    .map(|record| record.entry.eid)
+                                    .find(|start_eid| *start_eid >= eentry.eid)
+                                    .unwrap_or(u32::MAX);
+
+                                Ok(MemoryRWEntry {
+                                    entry: mentry.clone(),
+                                    start_eid: 0,
+                                    end_eid,
+                                })
+                            }
+                            resolved => resolved,
+                        },
+                    )
+                    .collect::<Result<Vec<_>, BuildError>>()?;
+
+                Ok(EventTableEntryWithMemoryInfo {
+                    eentry: eentry.clone(),
+                    memory_rw_entires,
+                })
+            })
+            .collect::<Result<Vec<_>, BuildError>>()?;
+
+        Ok(ResolvedEventTable {
+            event_table: EventTableWithMemoryInfo(entries),
+            synthetic_writes: synthetic_writes.into_values().collect(),
+        })
+    }
+
+    /// Same as `new`, along with the memory events of each step before the lifespan lookup.
+    pub fn new_with_raw_events(
+        event_table: &EventTable,
+        memory_writing_table: &MemoryWritingTable,
+    ) -> (Self, Vec<(u32, Vec<MemoryTableEntry>)>) {
+        let raw_events = event_table
+            .entries()
+            .iter()
+            .map(|eentry| (eentry.eid, memory_event_of_step(eentry)))
+            .collect();
+
+        (Self::new(event_table, memory_writing_table), raw_events)
+    }
+
+    /// Same as `new` for tables loaded from disk, e.g. the json dumps of a previous run, which
+    /// may be inconsistent with each other.
+    pub fn from_serialized(
+        event_table: EventTable,
+        mtable: MemoryWritingTable,
+    ) -> Result<Self, BuildError> {
+        let lookup = IntervalIndex::new(&mtable);
+
+        Ok(EventTableWithMemoryInfo(
+            event_table
+                .entries()
+                .iter()
+                .map(|eentry| {
+                    Ok(EventTableEntryWithMemoryInfo {
+                        eentry: eentry.clone(),
+                        memory_rw_entires: try_resolve_memory_rw_entries(&lookup, eentry)?,
+                    })
+                })
+                .collect::<Result<Vec<_>, BuildError>>()?,
+        ))
+    }
+
+    /// Walk the entries along with their (current, next) status, as constrained by the circuit.
+    /// `status` is expected to be the one returned by `EventTableChip::status_sequence`.
+    pub fn iter_with_status<'a, 'b>(
+        &'a self,
+        status: &'a [Status<'b>],
+    ) -> impl Iterator<
+        Item = (
+            &'a EventTableEntryWithMemoryInfo,
+            &'a Status<'b>,
+            &'a Status<'b>,
+        ),
+    > {
+        self.0
+            .iter()
+            .zip(status.iter().zip(status.iter().skip(1)))
+            .map(|(entry, (current, next))| (entry, current, next))
+    }
+
+    /// Reconstruct the tree of call frames from the `last_jump_eid` of the entries. A slice may
+    /// start or end within a callee, so there may be several root frames.
+    pub fn call_frames(&self) -> Vec<CallFrame> {
+        fn pop_into(stack: &mut Vec<CallFrame>, roots: &mut Vec<CallFrame>) {
+            let frame = stack.pop().unwrap();
+
+            match stack.last_mut() {
+                Some(parent) => {
+                    parent.entry_range.end = parent.entry_range.end.max(frame.entry_range.end);
+                    parent.children.push(frame);
+                }
+                None => roots.push(frame),
+            }
+        }
+
+        let mut roots = vec![];
+        let mut stack: Vec<CallFrame> = vec![];
+
+        for (index, entry) in self.0.iter().enumerate() {
+            let frame_id = entry.eentry.last_jump_eid;
+
+            if stack.iter().any(|frame| frame.frame_id == frame_id) {
+                // Returned to a frame on the stack.
+                while stack.last().unwrap().frame_id != frame_id {
+                    pop_into(&mut stack, &mut roots);
+                }
+            } else {
+                let is_callee = index > 0 && {
+                    let prev = &self.0[index - 1].eentry;
+
+                    prev.eid == frame_id
+                        && matches!(
+                            prev.step_info,
+                            StepInfo::Call { .. } | StepInfo::CallIndirect { .. }
+                        )
+                };
+
+                // Returned to a caller before the start of the slice.
+                if !is_callee {
+                    while !stack.is_empty() {
+                        pop_into(&mut stack, &mut roots);
+                    }
+                }
+
+                stack.push(CallFrame {
+                    frame_id,
+                    fid: entry.eentry.fid,
+                    entry_range: index..index,
+                    children: vec![],
+                });
+            }
+
+            stack.last_mut().unwrap().entry_range.end = index + 1;
+        }
+
+        while !stack.is_empty() {
+            pop_into(&mut stack, &mut roots);
+        }
+
+        roots
+    }
+
+    /// Check that every step has its sp within `[min_sp, max_sp]`, returning the first one that
+    /// doesn't.
+    pub fn validate_sp_bounds(&self, min_sp: u32, max_sp: u32) -> Result<(), SpViolation> {
+        match self
+            .0
+            .iter()
+            .find(|entry| entry.eentry.sp < min_sp || entry.eentry.sp > max_sp)
+        {
+            Some(entry) => Err(SpViolation {
+                eid: entry.eentry.eid,
+                sp: entry.eentry.sp,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// The source location of each step, looked up by the (fid, iid) of its instruction in
+    /// `debug_info`. None if the instruction has no debug info.
+    pub fn with_source_locations(
+        &self,
+        debug_info: &HashMap<(u32, u32), SourceLoc>,
+    ) -> Vec<(u32, Option<SourceLoc>)> {
+        self.0
+            .iter()
+            .map(|entry| {
+                (
+                    entry.eentry.eid,
+                    debug_info
+                        .get(&(entry.eentry.fid, entry.eentry.iid))
+                        .cloned(),
+                )
+            })
+            .collect()
+    }
+
+    /// The executed instructions as `fid.iid: opcode` listings, e.g. for comparing a run against
+    /// the expected behavior of the program.
+    pub fn disassemble(&self, itable: &InstructionTable) -> Vec<(u32, String)> {
+        self.0
+            .iter()
+            .map(|entry| {
+                let instruction = entry.eentry.get_instruction(itable);
+
+                (
+                    entry.eentry.eid,
+                    format!(
+                        "{}.{}: {:?}",
+                        entry.eentry.fid, entry.eentry.iid, instruction.opcode
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    /// `(read eid, ltype, offset, write eid)` of every read, where the write eid is the start of
+    /// the resolved lifespan, i.e. the step producing the value read. Ordered by eid.
+    pub fn read_provenance(&self) -> Vec<(u32, LocationType, u32, u32)> {
+        self.0
+            .iter()
+            .flat_map(|entry| {
+                entry
+                    .memory_rw_entires
+                    .iter()
+                    .filter(|rw| rw.entry.atype == AccessType::Read)
+                    .map(|rw| {
+                        (
+                            entry.eentry.eid,
+                            rw.entry.ltype,
+                            rw.entry.offset,
+                            rw.start_eid,
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Distinct fids of the functions executed in the trace.
+    pub fn executed_fids(&self) -> BTreeSet<u32> {
+        self.0.iter().map(|entry| entry.eentry.fid).collect()
+    }
+
+    /// Eids of the steps executing an instruction of `class`.
+    pub fn eids_of_class(&self, itable: &InstructionTable, class: OpcodeClassPlain) -> Vec<u32> {
+        self.0
+            .iter()
+            .filter(|entry| {
+                let entry_class: OpcodeClassPlain =
+                    (&entry.eentry.get_instruction(itable).opcode).into();
+
+                entry_class == class
+            })
+            .map(|entry| entry.eentry.eid)
+            .collect()
+    }
+
+    /// Number of steps per opcode class, ordered by class.
+    pub fn opcode_histogram(&self, itable: &InstructionTable) -> BTreeMap<OpcodeClassPlain, usize> {
+        let mut histogram = BTreeMap::new();
+
+        for entry in &self.0 {
+            let class: OpcodeClassPlain = (&entry.eentry.get_instruction(itable).opcode).into();
+
+            *histogram.entry(class).or_default() += 1;
+        }
+
+        histogram
+    }
+
+    pub fn write_opcode_histogram_json(&self, itable: &InstructionTable, dir: &Path) {
+        let histogram = serde_json::to_string_pretty(&self.opcode_histogram(itable)).unwrap();
+
+        MemoryWritingTable::write_file(dir, "opcode_histogram.json", &histogram);
+    }
+
+    /// Shrink the table to a small contiguous range of entries for which `predicate` still
+    /// reports the failure, `predicate` is expected to hold on the whole table.
+    ///
+    /// The memory writing table and the memory info are rebuilt from each candidate range, whose
+    /// memory is initialized with the values observed by its first step in `memory_writing_table`
+    /// of the whole trace.
+    pub fn minimize_failing(
+        &self,
+        k: u32,
+        memory_writing_table: &MemoryWritingTable,
+        predicate: impl Fn(&EventTableWithMemoryInfo, &MemoryWritingTable) -> bool,
+    ) -> (EventTableWithMemoryInfo, MemoryWritingTable) {
+        let index = IntervalIndex::new(memory_writing_table);
+
+        let sub_table = |start: usize, end: usize| {
+            let event_table = EventTable::new(
+                self.0[start..end]
+                    .iter()
+                    .map(|entry| entry.eentry.clone())
+                    .collect(),
+            );
+            let mtable = MemoryWritingTable::from(k, sub_trace_memory_table(&event_table, &index));
+
+            (EventTableWithMemoryInfo::new(&event_table, &mtable), mtable)
+        };
+        let fails = |start: usize, end: usize| {
+            let (event_table, mtable) = sub_table(start, end);
+
+            predicate(&event_table, &mtable)
+        };
+
+        let (mut start, mut end) = (0, self.0.len());
+        let mut chunk = self.0.len() / 2;
+
+        while chunk > 0 {
+            if end - start > chunk && fails(start, end - chunk) {
+                end -= chunk;
+            } else if end - start > chunk && fails(start + chunk, end) {
+                start += chunk;
+            } else {
+                chunk /= 2;
+            }
+        }
+
+        sub_table(start, end)
+    }
+
+    /// Check that the instruction of every step is present in `itable`, all missing ones are
+    /// reported.
+    pub fn verify_instructions_present(
+        &self,
+        itable: &InstructionTable,
+    ) -> Result<(), Vec<MissingInstruction>> {
+        let missing = self
+            .0
+            .iter()
+            .filter(|entry| itable.try_get(entry.eentry.fid, entry.eentry.iid).is_none())
+            .map(|entry| MissingInstruction {
+                eid: entry.eentry.eid,
+                fid: entry.eentry.fid,
+                iid: entry.eentry.iid,
+            })
+            .collect::<Vec<_>>();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
+    /// The encoded instruction looked up by each step, in execution order.
+    pub fn instruction_encodings(&self, itable: &InstructionTable) -> Vec<(u32, BigUint)> {
+        self.0
+            .iter()
+            .map(|entry| {
+                (
+                    entry.eentry.eid,
+                    entry.eentry.get_instruction(itable).encode.clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Entries of an event table along with their memory info, as iterated by the assignment of the
+/// event table chip.
+pub trait EventTableEntries: Send + Sync {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The event entry at `index`, which must be less than `len`.
+    fn event_entry(&self, index: usize) -> &EventTableEntry;
+
+    /// The entry at `index` with its memory info, which must be less than `len`.
+    fn entry(&self, index: usize) -> Cow<'_, EventTableEntryWithMemoryInfo>;
+
+    fn event_entries(&self) -> Box<dyn Iterator<Item = &EventTableEntry> + '_> {
+        Box::new((0..self.len()).map(|index| self.event_entry(index)))
+    }
+}
+
+impl EventTableEntries for EventTableWithMemoryInfo {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn event_entry(&self, index: usize) -> &EventTableEntry {
+        &self.0[index].eentry
+    }
+
+    fn entry(&self, index: usize) -> Cow<'_, EventTableEntryWithMemoryInfo> {
+        Cow::Borrowed(&self.0[index])
+    }
+}
+
+/// Event table built by `EventTableWithMemoryInfo::with_read_policy`. Lenient reads refer to
+/// synthetic writes absent from the memory writing table, so it only offers queries and converts
+/// into an assignable table when no read resolves to them.
+pub struct ResolvedEventTable {
+    event_table: EventTableWithMemoryInfo,
+    synthetic_writes: Vec<MemoryTableEntry>,
+}
+
+impl ResolvedEventTable {
+    pub fn entries(&self) -> &[EventTableEntryWithMemoryInfo] {
+        &self.event_table.0
+    }
+
+    /// The zero writes at eid 0 standing for the defaults of the globals read before being
+    /// written, one per global.
+    pub fn synthetic_writes(&self) -> &[MemoryTableEntry] {
+        &self.synthetic_writes
+    }
+
+    /// The assignable table, `None` if some read resolves to a synthetic write.
+    pub fn into_assignable(self) -> Option<EventTableWithMemoryInfo> {
+        self.synthetic_writes.is_empty().then_some(self.event_table)
+    }
+}
+
+/// Event table resolving the memory info of an entry on demand instead of holding it, which trades
+/// time for memory on large traces. `EventTableWithMemoryInfo` remains the default.
+pub struct LazyEventTableWithMemoryInfo<'a> {
+    event_table: &'a EventTable,
+    lookup: IntervalIndex<'a>,
+}
+
+impl<'a> LazyEventTableWithMemoryInfo<'a> {
+    pub fn new(event_table: &'a EventTable, memory_writing_table: &'a MemoryWritingTable) -> Self {
+        Self {
+            event_table,
+            lookup: IntervalIndex::new(memory_writing_table),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<EventTableEntryWithMemoryInfo> {
+        self.event_table
+            .entries()
+            .get(index)
+            .map(|eentry| EventTableEntryWithMemoryInfo {
+                eentry: eentry.clone(),
+                memory_rw_entires: resolve_memory_rw_entries(&self.lookup, eentry),
+            })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = EventTableEntryWithMemoryInfo> + '_ {
+        (0..self.len()).filter_map(|index| self.get(index))
+    }
+}
+
+impl EventTableEntries for LazyEventTableWithMemoryInfo<'_> {
+    fn len(&self) -> usize {
+        self.event_table.entries().len()
+    }
+
+    fn event_entry(&self, index: usize) -> &EventTableEntry {
+        &self.event_table.entries()[index]
+    }
+
+    fn entry(&self, index: usize) -> Cow<'_, EventTableEntryWithMemoryInfo> {
+        Cow::Owned(self.get(index).unwrap())
+    }
 }
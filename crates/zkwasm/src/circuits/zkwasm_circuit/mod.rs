@@ -14,6 +14,7 @@ use halo2_proofs::plonk::ConstraintSystem;
 use halo2_proofs::plonk::Error;
 use halo2_proofs::plonk::Fixed;
 use log::debug;
+use log::error;
 use log::info;
 use specs::etable::EventTable;
 use specs::jtable::JumpTable;
@@ -40,7 +41,9 @@ use crate::circuits::rtable::RangeTableConfig;
 use crate::circuits::utils::image_table::EncodeImageTable;
 use crate::circuits::utils::image_table::ImageTableAssigner;
 use crate::circuits::utils::image_table::ImageTableLayouter;
+use crate::circuits::utils::table_entry::EventTableEntries;
 use crate::circuits::utils::table_entry::EventTableWithMemoryInfo;
+use crate::circuits::utils::table_entry::LazyEventTableWithMemoryInfo;
 use crate::circuits::utils::table_entry::MemoryWritingTable;
 use crate::circuits::ZkWasmCircuit;
 use crate::exec_with_profile;
@@ -56,6 +59,8 @@ use crate::runtime::memory_event_of_step;
 
 use super::config::zkwasm_k;
 use super::etable::assign::EventTablePermutationCells;
+use super::etable::assign::PostState;
+use super::etable::assign::PreState;
 use super::image_table::ImageTableConfig;
 use super::post_image_table::PostImageTableConfig;
 
@@ -102,13 +107,25 @@ pub struct ZkWasmCircuitConfig<F: FieldExt> {
     k: u32,
 }
 
+#[cfg(test)]
+impl<F: FieldExt> ZkWasmCircuitConfig<F> {
+    /// The event table config, for reading its columns back from a MockProver.
+    pub(crate) fn etable(&self) -> &EventTableConfig<F> {
+        &self.etable
+    }
+
+    pub(crate) fn max_available_rows(&self) -> usize {
+        self.max_available_rows
+    }
+}
+
 impl<F: FieldExt> Circuit<F> for ZkWasmCircuit<F> {
     type Config = ZkWasmCircuitConfig<F>;
 
     type FloorPlanner = FlatFloorPlanner;
 
     fn without_witnesses(&self) -> Self {
-        ZkWasmCircuit::new(
+        let mut circuit = ZkWasmCircuit::new(
             self.k,
             // fill slice like circuit_without_witness
             Slice {
@@ -130,7 +147,13 @@ impl<F: FieldExt> Circuit<F> for ZkWasmCircuit<F> {
                 is_last_slice: self.slice.is_last_slice,
             },
         )
-        .unwrap()
+        .unwrap();
+
+        circuit.common_range_max = self.common_range_max;
+        circuit.etable_chip_options = self.etable_chip_options.clone();
+        circuit.lazy_memory_info = self.lazy_memory_info;
+
+        circuit
     }
 
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
@@ -236,6 +259,10 @@ impl<F: FieldExt> Circuit<F> for ZkWasmCircuit<F> {
             compute_slice_capability(self.k) as usize,
             config.max_available_rows,
         );
+        let echip = match self.etable_chip_options.as_ref() {
+            Some(options) => options(echip),
+            None => echip,
+        };
         let bit_chip = BitTableChip::new(config.bit_table, config.max_available_rows);
         let external_host_call_chip =
             ExternalHostCallChip::new(config.external_host_call_table, config.max_available_rows);
@@ -248,25 +275,46 @@ impl<F: FieldExt> Circuit<F> for ZkWasmCircuit<F> {
             config.circuit_maximal_pages,
         );
 
-        let memory_writing_table: MemoryWritingTable = MemoryWritingTable::from(
-            config.k,
+        let memory_writing_table = MemoryWritingTable::checked_from_with_common_range(
+            self.memory_common_range_max(),
             self.slice.create_memory_table(memory_event_of_step),
-        );
-
-        let etable = exec_with_profile!(
-            || "Prepare memory info for etable",
-            EventTableWithMemoryInfo::new(&self.slice.etable, &memory_writing_table,)
-        );
+        )
+        .map_err(|err| {
+            error!("{}", err);
+
+            Error::Synthesis
+        })?;
+
+        // Reject oversized traces before resolving the memory info of every entry.
+        echip
+            .check_trace_size(self.slice.etable.entries().len())
+            .map_err(|err| {
+                error!("{}", err);
+
+                Error::Synthesis
+            })?;
+
+        let etable: Arc<dyn EventTableEntries + '_> = if self.lazy_memory_info {
+            Arc::new(LazyEventTableWithMemoryInfo::new(
+                &self.slice.etable,
+                &memory_writing_table,
+            ))
+        } else {
+            Arc::new(exec_with_profile!(
+                || "Prepare memory info for etable",
+                EventTableWithMemoryInfo::new(&self.slice.etable, &memory_writing_table,)
+            ))
+        };
 
         let assigned_cells = AssignedCells::default();
 
         let layouter_cloned = layouter.clone();
         let assigned_cells_cloned = assigned_cells.clone();
 
-        rayon::scope(move |s| {
-            let memory_writing_table = Arc::new(memory_writing_table);
-            let etable = Arc::new(etable);
+        // Shared with the tasks by reference, since the lazy etable borrows it as well.
+        let memory_writing_table = &memory_writing_table;
 
+        rayon::scope(move |s| {
             let _layouter = layouter.clone();
             s.spawn(move |_| {
                 exec_with_profile!(
@@ -354,7 +402,7 @@ impl<F: FieldExt> Circuit<F> for ZkWasmCircuit<F> {
 
             let _layouter = layouter.clone();
             let _assigned_cells = assigned_cells.clone();
-            let _memory_writing_table = memory_writing_table.clone();
+            let _memory_writing_table = memory_writing_table;
             s.spawn(move |_| {
                 let post_image_table: ImageTableLayouter<F> =
                     self.slice.encode_post_compilation_table_values(config.k);
@@ -403,7 +451,7 @@ impl<F: FieldExt> Circuit<F> for ZkWasmCircuit<F> {
             s.spawn(move |_| {
                 exec_with_profile!(|| "Assign mtable", {
                     let (rest_mops, rest_memory_finalize_ops_cell) =
-                        mchip.assign(_layouter, &memory_writing_table).unwrap();
+                        mchip.assign(_layouter, memory_writing_table).unwrap();
 
                     *_assigned_cells.mtable_rest_mops.lock().unwrap() = Some(rest_mops);
                     *_assigned_cells
@@ -421,10 +469,10 @@ impl<F: FieldExt> Circuit<F> for ZkWasmCircuit<F> {
                         .assign(
                             _layouter,
                             &self.slice.itable,
-                            &etable,
+                            &*etable,
                             &self.slice.configure_table,
-                            &self.slice.initialization_state,
-                            &self.slice.post_initialization_state,
+                            PreState(&self.slice.initialization_state),
+                            PostState(&self.slice.post_initialization_state),
                             self.slice.is_last_slice,
                         )
                         .unwrap();
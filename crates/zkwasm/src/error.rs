@@ -1,5 +1,10 @@
+use num_bigint::BigUint;
+use specs::itable::OpcodeClassPlain;
+use specs::mtable::LocationType;
 use thiserror::Error;
 
+use crate::circuits::utils::step_status::StatusRecord;
+
 #[derive(Debug, Error)]
 pub enum CompilationError {}
 
@@ -14,4 +19,194 @@ pub enum BuildingCircuitError {
     PagesExceedLimit(u32, u32, u32),
     #[error("Etable entries({0}) exceed the limit({1}). Current K is {2}, consider increasing the circuit size K.")]
     EtableEntriesExceedLimit(u32, u32, u32),
+    #[error("Common range maximum({0}) exceeds the limit({1}). Current K is {2}, consider increasing the circuit size K.")]
+    CommonRangeExceedLimit(u32, u32, u32),
+}
+
+#[derive(Debug, Error)]
+pub enum RangeError {
+    #[error("Value {value} exceeds the field modulus.")]
+    ExceedsModulus { value: BigUint },
+    #[error("Value {value} exceeds the maximum {max}.")]
+    ExceedsMax { value: u64, max: u64 },
+}
+
+#[derive(Debug, Error)]
+pub enum EtableError {
+    #[error("Status index({index}) is out of range, the length of status is {len}.")]
+    StatusIndexOutOfRange { index: usize, len: usize },
+    #[error(
+        "Status length({actual}) mismatches, {expected} is expected including the terminal status."
+    )]
+    StatusLengthMismatch { expected: usize, actual: usize },
+    #[error("Offset of the event table entry at index {index} overflows.")]
+    OffsetOverflow { index: usize },
+    #[error("Trace of {len} entries exceeds the limit of {max} entries.")]
+    TraceTooLarge { len: usize, max: usize },
+    #[error("Event table entries({entries}) exceed the capability({capability}).")]
+    CapabilityExceeded { entries: usize, capability: usize },
+    #[error("Event table assignment ends at row {actual}, {expected} is expected.")]
+    RowCountMismatch { expected: usize, actual: usize },
+    #[error("Row group {group} has (sel, enable) flags {actual:?}, {expected:?} is expected.")]
+    LayoutMismatch {
+        group: usize,
+        expected: (bool, bool),
+        actual: (bool, bool),
+    },
+    #[error("Post initialization state ends at eid {actual}, {expected} is expected.")]
+    TerminalEidMismatch { expected: u32, actual: u32 },
+    #[error("Op selector index({index}) of {class:?} is out of range, there are {len} selectors.")]
+    OpSelectorOutOfRange {
+        class: OpcodeClassPlain,
+        index: usize,
+        len: usize,
+    },
+    #[error("Padding starts at row {offset}, beyond the end of padding {padding_end}.")]
+    PaddingOverrun { offset: usize, padding_end: usize },
+    #[error(
+        "The event table is empty but the post initialization state differs from the initial one."
+    )]
+    EmptySliceStateChanged,
+    #[error("The event table is empty, a trace is expected for assigning with witness.")]
+    MissingWitness,
+    #[error("The event table has {entries} entries, an empty one is expected for key generation.")]
+    UnexpectedWitness { entries: usize },
+    #[error("{field} decreases across the slice, from {pre} to {post}.")]
+    NonMonotonicState {
+        field: &'static str,
+        pre: u32,
+        post: u32,
+    },
+    #[error("Maximal memory pages disagree, pre state: {pre}, post state: {post}, configure table: {configured}.")]
+    MaximalMemoryPagesMismatch {
+        pre: u32,
+        post: u32,
+        configured: u32,
+    },
+    #[error("Allocated memory pages({allocated}) at eid {eid} exceed the maximal memory pages({maximal}).")]
+    MemoryPagesExceeded {
+        eid: u32,
+        allocated: u32,
+        maximal: u32,
+    },
+    #[error("Rest mops goes below zero at eid {eid}.")]
+    RestMopsUnderflow { eid: u32 },
+    #[error("Rest mops terminates at {rest_mops} rather than zero.")]
+    RestMopsNotTerminated { rest_mops: u32 },
+    #[error("Jops goes below zero at eid {eid}.")]
+    JopsUnderflow { eid: u32 },
+    #[error("Jops terminates at {jops} rather than zero.")]
+    JopsNotTerminated { jops: BigUint },
+    #[error("Jops({jops}) exceeds the field modulus.")]
+    JopsOverflow { jops: BigUint },
+    #[error("Jops at eid {eid} takes {bits} bits, exceeding the configured width.")]
+    JopsTooLarge { eid: u32, bits: u64 },
+    #[error("Failed assigning {label} at offset {offset} of capability {capability}: {source:?}")]
+    RegionAssignment {
+        label: &'static str,
+        offset: usize,
+        capability: usize,
+        source: halo2_proofs::plonk::Error,
+    },
+    #[error("Failed assigning the entry at index {index}(eid {eid}): {source}")]
+    EntryAssignment {
+        index: usize,
+        eid: u32,
+        // The status before the failing entry
+        status: Option<Box<StatusRecord>>,
+        source: Box<EtableError>,
+    },
+    #[error(transparent)]
+    Range(#[from] RangeError),
+    #[error(transparent)]
+    Halo2(#[from] halo2_proofs::plonk::Error),
+}
+
+#[derive(Debug, Error)]
+#[error("Rest mops folded from the op configs is {folded} but the entries write memory {recomputed} times.")]
+pub struct RestMopsMismatch {
+    pub folded: u32,
+    pub recomputed: u32,
+}
+
+#[derive(Debug, Error)]
+#[error("Context indices violate the ordering rule after eid {eid}, in: {context_in_index}, out: {context_out_index}.")]
+pub struct ContextOrderError {
+    pub eid: u32,
+    pub context_in_index: u32,
+    pub context_out_index: u32,
+}
+
+#[derive(Debug, Error)]
+pub enum TraceIssue {
+    #[error("Eid of the entry at index {index} is {actual}, {expected} is expected.")]
+    NonContiguousEid {
+        index: usize,
+        expected: u32,
+        actual: u32,
+    },
+    #[error("Read at eid {eid} of {ltype:?} offset {offset} is not covered by the memory writing table.")]
+    UncoveredRead {
+        eid: u32,
+        ltype: LocationType,
+        offset: u32,
+    },
+    #[error(transparent)]
+    Etable(#[from] EtableError),
+}
+
+#[derive(Debug, Error)]
+pub enum JopsChainError {
+    #[error("Expect (pre, post) state pairs, but {0} states are provided.")]
+    UnpairedStates(usize),
+    #[error("Jops of slice {slice} decreases from {pre} to {post}.")]
+    Decreasing {
+        slice: usize,
+        pre: BigUint,
+        post: BigUint,
+    },
+    #[error(
+        "Slice {slice} starts with jops {incoming} but the previous slice ends with {outgoing}."
+    )]
+    CarryOverMismatch {
+        slice: usize,
+        outgoing: BigUint,
+        incoming: BigUint,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum MTableError {
+    #[error("Duplicate memory write at eid {eid}, {ltype:?} offset {offset}.")]
+    DuplicateWrite {
+        eid: u32,
+        ltype: LocationType,
+        offset: u32,
+    },
+    #[error("The memory writing table is not grouped by location at {ltype:?} offset {offset}.")]
+    UngroupedLocation { ltype: LocationType, offset: u32 },
+}
+
+#[derive(Debug, Error)]
+pub enum BuildError {
+    #[error("Access at eid {eid} of {ltype:?} offset {offset} targets a location absent from the memory writing table.")]
+    UnknownLocation {
+        eid: u32,
+        ltype: LocationType,
+        offset: u32,
+    },
+    #[error(
+        "Write at eid {eid} of {ltype:?} offset {offset} is absent from the memory writing table."
+    )]
+    MissingWrite {
+        eid: u32,
+        ltype: LocationType,
+        offset: u32,
+    },
+    #[error("Read at eid {eid} of {ltype:?} offset {offset} is not covered by the memory writing table.")]
+    UncoveredRead {
+        eid: u32,
+        ltype: LocationType,
+        offset: u32,
+    },
 }
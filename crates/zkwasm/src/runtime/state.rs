@@ -62,110 +62,129 @@ impl UpdateInitializationState for InitializationState<u32, BigUint> {
         // None indicates last slice
         next_event_entry: Option<&EventTableEntry>,
     ) -> InitializationState<u32, BigUint> {
-        let mut host_public_inputs = self.host_public_inputs;
-        let mut context_in_index = self.context_in_index;
-        let mut context_out_index = self.context_out_index;
-        let mut external_host_call_call_index = self.external_host_call_call_index;
+        update_initialization_state_over(
+            self,
+            execution_table.entries(),
+            configure_table,
+            next_event_entry,
+        )
+    }
+}
 
-        #[cfg(feature = "continuation")]
-        let mut jops = self.jops.clone();
-
-        for entry in execution_table.entries() {
-            match &entry.step_info {
-                // TODO: fix hard code
-                StepInfo::CallHost {
-                    plugin,
-                    function_name,
-                    args,
-                    ..
-                } => {
-                    if *plugin == HostPlugin::HostInput {
-                        if (function_name == "wasm_input" && args[0] != 0)
-                            || function_name == "wasm_output"
-                        {
-                            host_public_inputs += 1;
-                        }
-                    } else if *plugin == HostPlugin::Context {
-                        if function_name == "wasm_read_context" {
-                            context_in_index += 1;
-                        } else if function_name == "wasm_write_context" {
-                            context_out_index += 1;
-                        }
-                    }
-                }
-                StepInfo::ExternalHostCall { .. } => external_host_call_call_index += 1,
-                StepInfo::Call { .. } | StepInfo::CallIndirect { .. } => {
-                    #[cfg(feature = "continuation")]
+/// Same as `update_initialization_state` over any sequence of entries, e.g. those of an
+/// `EventTableWithMemoryInfo`. The position of the state is kept if `entries` is empty and
+/// `next_event_entry` is None.
+pub(crate) fn update_initialization_state_over<'a>(
+    state: &InitializationState<u32, BigUint>,
+    entries: impl IntoIterator<Item = &'a EventTableEntry>,
+    configure_table: &ConfigureTable,
+    // None indicates last slice
+    next_event_entry: Option<&EventTableEntry>,
+) -> InitializationState<u32, BigUint> {
+    let mut host_public_inputs = state.host_public_inputs;
+    let mut context_in_index = state.context_in_index;
+    let mut context_out_index = state.context_out_index;
+    let mut external_host_call_call_index = state.external_host_call_call_index;
+
+    #[cfg(feature = "continuation")]
+    let mut jops = state.jops.clone();
+
+    let mut last_entry = None;
+
+    for entry in entries {
+        match &entry.step_info {
+            // TODO: fix hard code
+            StepInfo::CallHost {
+                plugin,
+                function_name,
+                args,
+                ..
+            } => {
+                if *plugin == HostPlugin::HostInput {
+                    if (function_name == "wasm_input" && args[0] != 0)
+                        || function_name == "wasm_output"
                     {
-                        jops += crate::circuits::jtable::encode_jops(0, 1);
+                        host_public_inputs += 1;
                     }
-                }
-                StepInfo::Return { .. } => {
-                    #[cfg(feature = "continuation")]
-                    {
-                        jops += crate::circuits::jtable::encode_jops(1, 0);
+                } else if *plugin == HostPlugin::Context {
+                    if function_name == "wasm_read_context" {
+                        context_in_index += 1;
+                    } else if function_name == "wasm_write_context" {
+                        context_out_index += 1;
                     }
                 }
-                _ => (),
             }
-        }
-
-        let post_initialization_state = if next_event_entry.is_none() {
-            let last_entry = execution_table.entries().last().unwrap();
-
-            InitializationState {
-                eid: last_entry.eid + 1,
-                fid: 0,
-                iid: 0,
-                frame_id: 0,
-                // TODO: why not constant 4095?
-                sp: last_entry.sp
-                    + if let StepInfo::Return { drop, .. } = last_entry.step_info {
-                        drop
-                    } else {
-                        0
-                    },
-
-                host_public_inputs,
-                context_in_index,
-                context_out_index,
-                external_host_call_call_index,
-
-                initial_memory_pages: last_entry.allocated_memory_pages,
-                maximal_memory_pages: configure_table.maximal_memory_pages,
-
+            StepInfo::ExternalHostCall { .. } => external_host_call_call_index += 1,
+            StepInfo::Call { .. } | StepInfo::CallIndirect { .. } => {
                 #[cfg(feature = "continuation")]
-                jops,
-
-                #[cfg(not(feature = "continuation"))]
-                _phantom: std::marker::PhantomData,
+                {
+                    jops += crate::circuits::jtable::encode_jops(0, 1);
+                }
             }
-        } else {
-            let next_entry = next_event_entry.unwrap();
-
-            InitializationState {
-                eid: next_entry.eid,
-                fid: next_entry.fid,
-                iid: next_entry.iid,
-                frame_id: next_entry.last_jump_eid,
-                sp: next_entry.sp,
-
-                host_public_inputs,
-                context_in_index,
-                context_out_index,
-                external_host_call_call_index,
+            StepInfo::Return { .. } => {
+                #[cfg(feature = "continuation")]
+                {
+                    jops += crate::circuits::jtable::encode_jops(1, 0);
+                }
+            }
+            _ => (),
+        }
 
-                initial_memory_pages: next_entry.allocated_memory_pages,
-                maximal_memory_pages: configure_table.maximal_memory_pages,
+        last_entry = Some(entry);
+    }
 
-                #[cfg(feature = "continuation")]
-                jops,
+    let (eid, fid, iid, frame_id, sp, initial_memory_pages) = match (next_event_entry, last_entry) {
+        (Some(next_entry), _) => (
+            next_entry.eid,
+            next_entry.fid,
+            next_entry.iid,
+            next_entry.last_jump_eid,
+            next_entry.sp,
+            next_entry.allocated_memory_pages,
+        ),
+        (None, Some(last_entry)) => (
+            last_entry.eid + 1,
+            0,
+            0,
+            0,
+            // TODO: why not constant 4095?
+            last_entry.sp
+                + if let StepInfo::Return { drop, .. } = last_entry.step_info {
+                    drop
+                } else {
+                    0
+                },
+            last_entry.allocated_memory_pages,
+        ),
+        (None, None) => (
+            state.eid,
+            state.fid,
+            state.iid,
+            state.frame_id,
+            state.sp,
+            state.initial_memory_pages,
+        ),
+    };
+
+    InitializationState {
+        eid,
+        fid,
+        iid,
+        frame_id,
+        sp,
+
+        host_public_inputs,
+        context_in_index,
+        context_out_index,
+        external_host_call_call_index,
+
+        initial_memory_pages,
+        maximal_memory_pages: configure_table.maximal_memory_pages,
 
-                #[cfg(not(feature = "continuation"))]
-                _phantom: std::marker::PhantomData,
-            }
-        };
+        #[cfg(feature = "continuation")]
+        jops,
 
-        post_initialization_state
+        #[cfg(not(feature = "continuation"))]
+        _phantom: std::marker::PhantomData,
     }
 }
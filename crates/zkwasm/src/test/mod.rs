@@ -1,3 +1,7 @@
+use crate::circuits::etable::EventTableChip;
+use crate::circuits::utils::table_entry::EventTableWithMemoryInfo;
+use crate::circuits::utils::table_entry::MemoryWritingTable;
+use crate::circuits::ZkWasmCircuit;
 use crate::loader::slice::Slices;
 use crate::loader::ZkWasmLoader;
 use crate::runtime::host::default_env::DefaultHostEnvBuilder;
@@ -7,6 +11,10 @@ use crate::runtime::monitor::table_monitor::TableMonitor;
 
 use anyhow::Result;
 use halo2_proofs::pairing::bn256::Fr;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use specs::slice::Slice;
+use specs::Tables;
 use specs::TraceBackend;
 use wabt::wat2wasm_with_features;
 use wabt::Features;
@@ -14,10 +22,14 @@ use wabt::Features;
 mod test_wasm_instructions;
 
 mod spec;
+mod test_etable_layout;
+mod test_opcode_class;
 mod test_rlp;
 #[cfg(feature = "continuation")]
 mod test_rlp_slice;
 mod test_start;
+mod test_step_status;
+mod test_table_entry;
 
 /// Run the function and generate trace, then test circuit with mock prover.
 pub fn test_circuit_with_env(
@@ -27,6 +39,22 @@ pub fn test_circuit_with_env(
     public_inputs: Vec<u64>,
     private_inputs: Vec<u64>,
 ) -> Result<()> {
+    let (tables, instances) =
+        execute_with_env(k, wasm, function_name, public_inputs, private_inputs)?;
+
+    Slices::new(k, tables)?.mock_test_all(k, instances)?;
+
+    Ok(())
+}
+
+/// Run the function and generate trace, returning the tables and the instances.
+fn execute_with_env(
+    k: u32,
+    wasm: Vec<u8>,
+    function_name: String,
+    public_inputs: Vec<u64>,
+    private_inputs: Vec<u64>,
+) -> Result<(Tables, Vec<Fr>)> {
     let module = ZkWasmLoader::parse_module(&wasm)?;
 
     let env = DefaultHostEnvBuilder.create_env(
@@ -46,9 +74,7 @@ pub fn test_circuit_with_env(
     let execution_result = loader.run(runner, &mut monitor)?;
     let instances: Vec<Fr> = execution_result.public_inputs_and_outputs();
 
-    Slices::new(k, monitor.into_tables())?.mock_test_all(k, instances)?;
-
-    Ok(())
+    Ok((monitor.into_tables(), instances))
 }
 
 /// Run test function and generate trace, then test circuit with mock prover. Only tests should
@@ -65,3 +91,81 @@ fn test_circuit_noexternal(textual_repr: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Accesses the stack, a global and the heap, and calls a function.
+const TEXTUAL_REPR_WITH_MEMORY: &str = r#"
+    (module
+        (memory $0 1)
+        (global $g (mut i32) (i32.const 0))
+        (func $inc (param i32) (result i32)
+          (i32.add (local.get 0) (i32.const 1))
+        )
+        (func (export "test")
+          (local i32)
+          (local.set 0 (i32.const 1))
+          (global.set $g (call $inc (local.get 0)))
+          (i32.store (i32.const 0) (global.get $g))
+          (i32.store (i32.const 8) (i32.const 3))
+          (i32.store (i32.const 8) (i32.const 3))
+          (drop (i32.load (i32.const 0)))
+        )
+    )
+    "#;
+
+/// Reads a global before writing it.
+const TEXTUAL_REPR_READ_BEFORE_WRITE: &str = r#"
+    (module
+        (global $g (mut i32) (i32.const 0))
+        (func (export "test")
+          (drop (global.get $g))
+          (global.set $g (i32.const 1))
+        )
+    )
+    "#;
+
+/// A slice of a traced program along with the memory info of its event table.
+struct TracedSlice {
+    slice: Slice,
+    memory_writing_table: MemoryWritingTable,
+    event_table: EventTableWithMemoryInfo,
+}
+
+/// Run the "test" export of `textual_repr` and resolve the memory info of each slice, for testing
+/// the queries over the tables.
+fn trace_slices(textual_repr: &str) -> Vec<TracedSlice> {
+    use crate::circuits::config::MIN_K;
+    use crate::runtime::memory_event_of_step;
+
+    let wasm = wabt::wat2wasm(&textual_repr).expect("failed to parse wat");
+    let (tables, _) = execute_with_env(MIN_K, wasm, "test".to_string(), vec![], vec![]).unwrap();
+
+    Slices::<Fr>::new(MIN_K, tables)
+        .unwrap()
+        .map(|circuit| {
+            let slice = circuit.unwrap().slice;
+            let memory_writing_table =
+                MemoryWritingTable::from(MIN_K, slice.create_memory_table(memory_event_of_step));
+            let event_table = EventTableWithMemoryInfo::new(&slice.etable, &memory_writing_table);
+
+            TracedSlice {
+                slice,
+                memory_writing_table,
+                event_table,
+            }
+        })
+        .collect()
+}
+
+/// The event table chip configured as by `ZkWasmCircuit`, with the maximal capability of MIN_K.
+/// Configuring reads the k set by tracing.
+fn etable_chip() -> EventTableChip<Fr> {
+    use crate::circuits::config::MIN_K;
+
+    let config = ZkWasmCircuit::<Fr>::configure(&mut ConstraintSystem::default());
+
+    EventTableChip::new_with_max_capability(
+        config.etable().clone(),
+        MIN_K,
+        config.max_available_rows(),
+    )
+}
@@ -0,0 +1,876 @@
+mod tests {
+    use halo2_proofs::arithmetic::FieldExt;
+    use halo2_proofs::circuit::floor_planner::FlatFloorPlanner;
+    use halo2_proofs::circuit::Layouter;
+    use halo2_proofs::dev::CellValue;
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pairing::bn256::Bn256;
+    use halo2_proofs::pairing::bn256::Fr;
+    use halo2_proofs::pairing::bn256::G1Affine;
+    use halo2_proofs::plonk::keygen_vk;
+    use halo2_proofs::plonk::Circuit;
+    use halo2_proofs::plonk::ConstraintSystem;
+    use halo2_proofs::plonk::Error;
+    use halo2_proofs::poly::commitment::Params;
+    use num_bigint::BigUint;
+    use specs::configure_table::ConfigureTable;
+    use specs::state::InitializationState;
+    use std::sync::Mutex;
+
+    use crate::circuits::compute_slice_capability;
+    use crate::circuits::config::MIN_K;
+    use crate::circuits::etable::assign::verify_step_layout;
+    use crate::circuits::etable::assign::PostState;
+    use crate::circuits::etable::assign::PreState;
+    use crate::circuits::etable::assign::SliceInput;
+    use crate::circuits::etable::EventTableChip;
+    use crate::circuits::etable::EVENT_TABLE_ENTRY_ROWS;
+    use crate::circuits::utils::bn_to_field;
+    use crate::circuits::utils::table_entry::EventTableEntries;
+    use crate::circuits::utils::table_entry::EventTableWithMemoryInfo;
+    use crate::circuits::utils::table_entry::MemoryWritingTable;
+    use crate::circuits::zkwasm_circuit::ZkWasmCircuitConfig;
+    use crate::circuits::ZkWasmCircuit;
+    use crate::error::EtableError;
+    use crate::error::TraceIssue;
+    use crate::loader::slice::Slices;
+    use crate::runtime::memory_event_of_step;
+    use crate::test::etable_chip;
+    use crate::test::execute_with_env;
+    use crate::test::trace_slices;
+    use crate::test::TracedSlice;
+    use crate::test::TEXTUAL_REPR_READ_BEFORE_WRITE;
+    use crate::test::TEXTUAL_REPR_WITH_MEMORY;
+
+    #[test]
+    fn test_full_slice_layout() {
+        let layout = [(true, true), (true, true), (true, true), (false, false)];
+
+        assert!(verify_step_layout(3, 3, &layout).is_ok());
+    }
+
+    #[test]
+    fn test_padded_slice_layout() {
+        let layout = [(true, true), (true, false), (true, false), (false, false)];
+
+        assert!(verify_step_layout(3, 1, &layout).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_layout() {
+        // enabled padding
+        let layout = [(true, true), (true, true), (true, false), (false, false)];
+        assert!(verify_step_layout(3, 1, &layout).is_err());
+
+        // selected post state
+        let layout = [(true, true), (true, false), (true, false), (true, false)];
+        assert!(verify_step_layout(3, 1, &layout).is_err());
+
+        // unselected padding
+        let layout = [(true, true), (false, false), (true, false), (false, false)];
+        assert!(verify_step_layout(3, 1, &layout).is_err());
+    }
+
+    #[test]
+    fn test_max_capability_for_k() {
+        for k in MIN_K..=25 {
+            let capability = EventTableChip::<Fr>::max_capability_for_k(k).unwrap();
+
+            assert!(
+                capability * EVENT_TABLE_ENTRY_ROWS as usize
+                    + EventTableChip::<Fr>::reserved_rows()
+                    <= 1 << k
+            );
+        }
+
+        // Fewer rows than the reserved ones.
+        assert_eq!(EventTableChip::<Fr>::max_capability_for_k(0), None);
+        assert_eq!(EventTableChip::<Fr>::max_capability_for_k(1), None);
+        // Just the reserved rows.
+        assert_eq!(EventTableChip::<Fr>::max_capability_for_k(2), Some(0));
+        // 2^k overflows.
+        assert_eq!(
+            EventTableChip::<Fr>::max_capability_for_k(usize::BITS),
+            None
+        );
+    }
+
+    // Verify each slice with a MockProver, then pass it to `check` along with the prover.
+    fn mock_prove_slices(
+        textual_repr: &str,
+        options: impl Fn(ZkWasmCircuit<Fr>) -> ZkWasmCircuit<Fr>,
+        check: impl Fn(&ZkWasmCircuit<Fr>, &MockProver<Fr>, &[Fr]),
+    ) {
+        let wasm = wabt::wat2wasm(&textual_repr).expect("failed to parse wat");
+        let (tables, instances) =
+            execute_with_env(MIN_K, wasm, "test".to_string(), vec![], vec![]).unwrap();
+
+        for slice in Slices::<Fr>::new(MIN_K, tables).unwrap() {
+            let circuit = options(slice.unwrap());
+            let prover = MockProver::run(MIN_K, &circuit, vec![instances.clone()]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+
+            check(&circuit, &prover, &instances);
+        }
+    }
+
+    fn mock_test_slices(
+        textual_repr: &str,
+        options: impl Fn(ZkWasmCircuit<Fr>) -> ZkWasmCircuit<Fr>,
+    ) {
+        mock_prove_slices(textual_repr, options, |_, _, _| ());
+    }
+
+    // Unassigned cells read as zero.
+    fn assigned_value<F: FieldExt>(column: &[CellValue<F>], row: usize) -> F {
+        match column[row] {
+            CellValue::Assigned(value) => value,
+            _ => F::zero(),
+        }
+    }
+
+    // Same columns as the circuit the prover was run with, configuring is deterministic.
+    fn circuit_config() -> ZkWasmCircuitConfig<Fr> {
+        ZkWasmCircuit::<Fr>::configure(&mut ConstraintSystem::default())
+    }
+
+    // The regions of FlatFloorPlanner start at row 0, so rows of the region are rows of the prover.
+    fn check_enabled_steps(circuit: &ZkWasmCircuit<Fr>, prover: &MockProver<Fr>) {
+        let config = circuit_config();
+        let capability = compute_slice_capability(MIN_K) as usize;
+
+        let enabled = config
+            .etable()
+            .count_enabled_cells(capability, |column, row| {
+                assigned_value(&prover.advice()[column.index()], row)
+            });
+        assert_eq!(enabled, circuit.slice.etable.entries().len());
+
+        config.etable().assert_step_sel_count(
+            capability,
+            (capability + 1) * EVENT_TABLE_ENTRY_ROWS as usize,
+            |column, row| assigned_value(&prover.fixed()[column.index()], row),
+        );
+    }
+
+    #[test]
+    fn test_enabled_steps() {
+        mock_prove_slices(
+            TEXTUAL_REPR_WITH_MEMORY,
+            |circuit| circuit,
+            |circuit, prover, _| check_enabled_steps(circuit, prover),
+        );
+    }
+
+    #[test]
+    fn test_enabled_steps_with_sparse_padding() {
+        mock_prove_slices(
+            TEXTUAL_REPR_WITH_MEMORY,
+            |circuit| circuit.with_etable_chip_options(|chip| chip.with_sparse_padding(true)),
+            |circuit, prover, _| check_enabled_steps(circuit, prover),
+        );
+    }
+
+    // Assigns the entry at `index` of the event table of `circuit` alone, at its offset in the
+    // full table.
+    struct SingleEntryCircuit<'a> {
+        circuit: &'a ZkWasmCircuit<Fr>,
+        index: usize,
+    }
+
+    impl Circuit<Fr> for SingleEntryCircuit<'_> {
+        type Config = ZkWasmCircuitConfig<Fr>;
+
+        type FloorPlanner = FlatFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            SingleEntryCircuit {
+                circuit: self.circuit,
+                index: self.index,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            ZkWasmCircuit::<Fr>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let slice = &self.circuit.slice;
+
+            let echip = EventTableChip::new_with_max_capability(
+                config.etable().clone(),
+                MIN_K,
+                config.max_available_rows(),
+            );
+
+            let memory_writing_table =
+                MemoryWritingTable::from(MIN_K, slice.create_memory_table(memory_event_of_step));
+            let event_table = EventTableWithMemoryInfo::new(&slice.etable, &memory_writing_table);
+
+            let status = echip
+                .status_sequence(
+                    &slice.itable,
+                    &event_table,
+                    PreState(&slice.initialization_state),
+                    PostState(&slice.post_initialization_state),
+                )
+                .unwrap();
+
+            layouter.assign_region(
+                || "event table",
+                |region| {
+                    echip
+                        .assign_single_entry(
+                            region,
+                            self.index * EVENT_TABLE_ENTRY_ROWS as usize,
+                            &event_table.entry(self.index),
+                            &status[self.index],
+                            &status[self.index + 1],
+                            &slice.configure_table,
+                        )
+                        .map_err(|_| Error::Synthesis)
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_assign_single_entry() {
+        mock_prove_slices(
+            TEXTUAL_REPR_WITH_MEMORY,
+            |circuit| circuit,
+            |circuit, prover, instances| {
+                let (enabled_column, enabled_rot) = circuit_config().etable().enabled_cell_column();
+
+                for index in 0..circuit.slice.etable.entries().len() {
+                    let single = MockProver::run(
+                        MIN_K,
+                        &SingleEntryCircuit { circuit, index },
+                        vec![instances.to_vec()],
+                    )
+                    .unwrap();
+
+                    let step = index * EVENT_TABLE_ENTRY_ROWS as usize;
+
+                    assert_eq!(
+                        assigned_value(
+                            &single.advice()[enabled_column.index()],
+                            step + enabled_rot as usize
+                        ),
+                        Fr::from(1)
+                    );
+
+                    // Every cell of the single step is assigned as in the full table.
+                    for (single_column, column) in
+                        single.advice().iter().zip(prover.advice().iter())
+                    {
+                        for row in step..step + EVENT_TABLE_ENTRY_ROWS as usize {
+                            if let CellValue::Assigned(value) = single_column[row] {
+                                assert_eq!(
+                                    assigned_value(column, row),
+                                    value,
+                                    "entry {} diverges at row {}",
+                                    index,
+                                    row
+                                );
+                            }
+                        }
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_sparse_padding() {
+        let textual_repr = r#"
+        (module
+            (func (export "test")
+              (i32.const 0)
+              (drop)
+            )
+        )
+        "#;
+
+        mock_test_slices(textual_repr, |circuit| {
+            circuit.with_etable_chip_options(|chip| chip.with_sparse_padding(true))
+        });
+    }
+
+    #[test]
+    fn test_lazy_memory_info() {
+        mock_test_slices(TEXTUAL_REPR_WITH_MEMORY, |circuit| {
+            circuit.with_lazy_memory_info(true)
+        });
+    }
+
+    #[cfg(not(feature = "continuation"))]
+    #[test]
+    fn test_jops_underflow() {
+        use specs::step::StepInfo;
+
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let chip = etable_chip();
+
+        // The call is the first step with jops.
+        let call_eid = traced
+            .slice
+            .etable
+            .entries()
+            .iter()
+            .find(|entry| matches!(entry.step_info, StepInfo::Call { .. }))
+            .unwrap()
+            .eid;
+
+        // Starting from no jops, rest_mops is large enough to never go below zero.
+        let err = chip
+            .compute_status(
+                &traced.slice.itable,
+                &traced.event_table,
+                PreState(&traced.slice.initialization_state),
+                PostState(&traced.slice.post_initialization_state),
+                u32::MAX,
+                BigUint::from(0u64),
+            )
+            .err();
+
+        assert!(matches!(err, Some(EtableError::JopsUnderflow { eid }) if eid == call_eid));
+    }
+
+    #[cfg(not(feature = "continuation"))]
+    #[test]
+    fn test_fits_single_proof_checks_jops() {
+        use crate::circuits::jtable::encode_jops;
+        use crate::circuits::jtable::frame_table_rest_jops;
+
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let chip = etable_chip();
+
+        let rest_jops =
+            frame_table_rest_jops(&traced.slice.static_jtable, &traced.slice.frame_table);
+
+        assert!(chip.fits_single_proof(
+            &traced.slice.itable,
+            &traced.event_table,
+            PreState(&traced.slice.initialization_state),
+            &rest_jops,
+        ));
+
+        // The trace fits in the capability but leaves a call of the frame table unconsumed.
+        assert!(!chip.fits_single_proof(
+            &traced.slice.itable,
+            &traced.event_table,
+            PreState(&traced.slice.initialization_state),
+            &(rest_jops + encode_jops(0, 1)),
+        ));
+    }
+
+    // Assigns the event table of `traced` alone and records its public inputs, with witness or
+    // as for key generation.
+    struct PublicInputsCircuit<'a> {
+        traced: &'a TracedSlice,
+        witness: bool,
+        public_inputs: &'a Mutex<Vec<Option<Vec<Fr>>>>,
+    }
+
+    impl Circuit<Fr> for PublicInputsCircuit<'_> {
+        type Config = ZkWasmCircuitConfig<Fr>;
+
+        type FloorPlanner = FlatFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            PublicInputsCircuit {
+                witness: false,
+                ..*self
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            ZkWasmCircuit::<Fr>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let slice = &self.traced.slice;
+
+            let echip = EventTableChip::new_with_max_capability(
+                config.etable().clone(),
+                MIN_K,
+                config.max_available_rows(),
+            );
+
+            let cells = if self.witness {
+                echip.assign_with_witness(
+                    layouter,
+                    &slice.itable,
+                    &self.traced.event_table,
+                    &slice.configure_table,
+                    PreState(&slice.initialization_state),
+                    PostState(&slice.post_initialization_state),
+                    slice.is_last_slice,
+                )
+            } else {
+                echip.assign_for_keygen(
+                    layouter,
+                    &slice.itable,
+                    &EventTableWithMemoryInfo::empty(),
+                    &slice.configure_table,
+                    PreState(&slice.initialization_state),
+                    PostState(&slice.initialization_state),
+                    slice.is_last_slice,
+                )
+            }
+            .map_err(|_| Error::Synthesis)?;
+
+            self.public_inputs
+                .lock()
+                .unwrap()
+                .push(cells.public_inputs());
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_public_inputs() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let public_inputs = Mutex::new(vec![]);
+
+        let circuit = PublicInputsCircuit {
+            traced: &traced[0],
+            witness: true,
+            public_inputs: &public_inputs,
+        };
+
+        // The other tables are left unassigned, the prover is not verified.
+        MockProver::run(MIN_K, &circuit, vec![vec![]]).unwrap();
+        assert!(public_inputs.lock().unwrap().pop().unwrap().is_some());
+
+        let params = Params::<G1Affine>::unsafe_setup::<Bn256>(MIN_K);
+        keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+        assert!(public_inputs.lock().unwrap().pop().unwrap().is_none());
+    }
+
+    // Assigns the event tables of `traced` in a batch and records their public inputs.
+    struct BatchCircuit<'a> {
+        traced: [&'a TracedSlice; 2],
+        public_inputs: &'a Mutex<Vec<Option<Vec<Fr>>>>,
+    }
+
+    impl Circuit<Fr> for BatchCircuit<'_> {
+        type Config = ZkWasmCircuitConfig<Fr>;
+
+        type FloorPlanner = FlatFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            BatchCircuit {
+                traced: self.traced,
+                public_inputs: self.public_inputs,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            ZkWasmCircuit::<Fr>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let echip = EventTableChip::new_with_max_capability(
+                config.etable().clone(),
+                MIN_K,
+                config.max_available_rows(),
+            );
+
+            let slices = self
+                .traced
+                .iter()
+                .map(|traced| SliceInput {
+                    itable: &traced.slice.itable,
+                    event_table: &traced.event_table,
+                    configure_table: &traced.slice.configure_table,
+                    initialization_state: PreState(&traced.slice.initialization_state),
+                    post_initialization_state: PostState(&traced.slice.post_initialization_state),
+                    is_last_slice: traced.slice.is_last_slice,
+                })
+                .collect::<Vec<_>>();
+
+            let cells = echip
+                .assign_batch(&mut layouter, &slices)
+                .map_err(|_| Error::Synthesis)?;
+
+            *self.public_inputs.lock().unwrap() =
+                cells.iter().map(|cells| cells.public_inputs()).collect();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_assign_batch() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY)
+            .into_iter()
+            .chain(trace_slices(TEXTUAL_REPR_READ_BEFORE_WRITE))
+            .collect::<Vec<_>>();
+        let public_inputs = Mutex::new(vec![]);
+
+        let circuit = BatchCircuit {
+            traced: [&traced[0], &traced[1]],
+            public_inputs: &public_inputs,
+        };
+
+        // The other tables are left unassigned, the prover is not verified.
+        MockProver::run(MIN_K, &circuit, vec![vec![]]).unwrap();
+
+        let public_inputs = public_inputs.into_inner().unwrap();
+        assert_eq!(public_inputs.len(), 2);
+
+        // Each slice exposes its own boundary states.
+        for (traced, public_inputs) in circuit.traced.iter().zip(public_inputs) {
+            let to_field = |state: &InitializationState<u32, BigUint>| {
+                state
+                    .map(|v| Fr::from(*v as u64), |v| bn_to_field(v))
+                    .plain()
+            };
+
+            let expected = to_field(&traced.slice.initialization_state)
+                .into_iter()
+                .chain(to_field(&traced.slice.post_initialization_state))
+                .collect::<Vec<_>>();
+
+            assert_eq!(public_inputs.unwrap()[..expected.len()], expected[..]);
+        }
+    }
+
+    #[test]
+    fn test_fits() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let entries = traced.event_table.len() as i64;
+
+        let chip = etable_chip();
+        let capability = chip.remaining_capacity(&EventTableWithMemoryInfo::empty());
+
+        assert!(chip.fits(&traced.event_table));
+        assert_eq!(
+            chip.remaining_capacity(&traced.event_table),
+            capability - entries
+        );
+
+        // A capability of two entries can't hold the trace.
+        let chip = EventTableChip::new_with_max_capability(
+            circuit_config().etable().clone(),
+            MIN_K,
+            2 * EVENT_TABLE_ENTRY_ROWS as usize,
+        );
+
+        assert!(!chip.fits(&traced.event_table));
+        assert_eq!(chip.remaining_capacity(&traced.event_table), 2 - entries);
+        assert!(chip.remaining_capacity(&traced.event_table) < 0);
+    }
+
+    // Assigns the event table of `traced`, or the empty one as for key generation, between the
+    // given states and records the error of the assignment.
+    struct CheckedStateCircuit<'a> {
+        traced: &'a TracedSlice,
+        witness: bool,
+        configure_table: ConfigureTable,
+        initialization_state: &'a InitializationState<u32, BigUint>,
+        post_initialization_state: &'a InitializationState<u32, BigUint>,
+        error: &'a Mutex<Option<EtableError>>,
+    }
+
+    impl<'a> CheckedStateCircuit<'a> {
+        // Assigns `traced` between its own states.
+        fn new(traced: &'a TracedSlice, error: &'a Mutex<Option<EtableError>>) -> Self {
+            CheckedStateCircuit {
+                traced,
+                witness: true,
+                configure_table: traced.slice.configure_table,
+                initialization_state: &traced.slice.initialization_state,
+                post_initialization_state: &traced.slice.post_initialization_state,
+                error,
+            }
+        }
+    }
+
+    impl Circuit<Fr> for CheckedStateCircuit<'_> {
+        type Config = ZkWasmCircuitConfig<Fr>;
+
+        type FloorPlanner = FlatFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            CheckedStateCircuit {
+                witness: false,
+                ..*self
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            ZkWasmCircuit::<Fr>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let slice = &self.traced.slice;
+
+            let echip = EventTableChip::new_with_max_capability(
+                config.etable().clone(),
+                MIN_K,
+                config.max_available_rows(),
+            );
+
+            let result = if self.witness {
+                echip.assign_with_witness(
+                    layouter,
+                    &slice.itable,
+                    &self.traced.event_table,
+                    &self.configure_table,
+                    PreState(self.initialization_state),
+                    PostState(self.post_initialization_state),
+                    slice.is_last_slice,
+                )
+            } else {
+                echip.assign_for_keygen(
+                    layouter,
+                    &slice.itable,
+                    &EventTableWithMemoryInfo::empty(),
+                    &self.configure_table,
+                    PreState(self.initialization_state),
+                    PostState(self.post_initialization_state),
+                    slice.is_last_slice,
+                )
+            };
+
+            let error = result.err();
+            let failed = error.is_some();
+            *self.error.lock().unwrap() = error;
+
+            if failed {
+                Err(Error::Synthesis)
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_slice_state_changed() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let error = Mutex::new(None);
+
+        // Without witness, the slice starts and ends at the same state.
+        let circuit = CheckedStateCircuit {
+            post_initialization_state: &traced[0].slice.initialization_state,
+            ..CheckedStateCircuit::new(&traced[0], &error)
+        }
+        .without_witnesses();
+        assert!(MockProver::run(MIN_K, &circuit, vec![vec![]]).is_ok());
+        assert!(error.lock().unwrap().is_none());
+
+        // The empty table can't move the state to the one after the trace.
+        let circuit = CheckedStateCircuit::new(&traced[0], &error).without_witnesses();
+        assert!(MockProver::run(MIN_K, &circuit, vec![vec![]]).is_err());
+        assert!(matches!(
+            *error.lock().unwrap(),
+            Some(EtableError::EmptySliceStateChanged)
+        ));
+    }
+
+    #[test]
+    fn test_non_monotonic_state() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let error = Mutex::new(None);
+
+        let circuit = CheckedStateCircuit::new(traced, &error);
+        MockProver::run(MIN_K, &circuit, vec![vec![]]).unwrap();
+        assert!(error.lock().unwrap().is_none());
+
+        // Swapping the states moves the execution backward.
+        let circuit = CheckedStateCircuit {
+            initialization_state: &traced.slice.post_initialization_state,
+            post_initialization_state: &traced.slice.initialization_state,
+            ..CheckedStateCircuit::new(traced, &error)
+        };
+        assert!(MockProver::run(MIN_K, &circuit, vec![vec![]]).is_err());
+        assert!(matches!(
+            *error.lock().unwrap(),
+            Some(EtableError::NonMonotonicState { field: "eid", pre, post })
+                if pre == traced.slice.post_initialization_state.eid
+                    && post == traced.slice.initialization_state.eid
+        ));
+    }
+
+    #[test]
+    fn test_validate_trace() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let chip = etable_chip();
+
+        let validate =
+            |configure_table: &ConfigureTable,
+             initialization_state: &InitializationState<u32, BigUint>| {
+                chip.validate_trace(
+                    &traced.slice.itable,
+                    &traced.event_table,
+                    &traced.memory_writing_table,
+                    configure_table,
+                    PreState(initialization_state),
+                )
+            };
+
+        assert!(validate(
+            &traced.slice.configure_table,
+            &traced.slice.initialization_state
+        )
+        .is_ok());
+
+        // The program grows no memory but still declares a page.
+        let configure_table = ConfigureTable {
+            maximal_memory_pages: 0,
+            ..traced.slice.configure_table
+        };
+        let issues = validate(&configure_table, &traced.slice.initialization_state).unwrap_err();
+        assert!(issues.iter().any(|issue| matches!(
+            issue,
+            TraceIssue::Etable(EtableError::MemoryPagesExceeded { maximal: 0, .. })
+        )));
+
+        // The trace doesn't start at the eid of the initialization state.
+        let initialization_state = InitializationState {
+            eid: traced.slice.initialization_state.eid + 1,
+            ..(*traced.slice.initialization_state).clone()
+        };
+        let issues = validate(&traced.slice.configure_table, &initialization_state).unwrap_err();
+        assert!(issues
+            .iter()
+            .any(|issue| matches!(issue, TraceIssue::NonContiguousEid { index: 0, .. })));
+    }
+
+    #[cfg(feature = "continuation")]
+    #[test]
+    fn test_validate_jops_chain() {
+        use specs::state::InitializationStateBuilder;
+
+        use crate::circuits::etable::assign::validate_jops_chain;
+        use crate::error::JopsChainError;
+
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let states = traced
+            .iter()
+            .flat_map(|traced| {
+                [
+                    (*traced.slice.initialization_state).clone(),
+                    (*traced.slice.post_initialization_state).clone(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        assert!(validate_jops_chain(&states).is_ok());
+
+        let state = |jops: u64| {
+            InitializationStateBuilder::new()
+                .jops(BigUint::from(jops))
+                .build()
+        };
+
+        assert!(validate_jops_chain(&[state(0), state(2), state(2), state(5)]).is_ok());
+
+        assert!(matches!(
+            validate_jops_chain(&[state(0), state(2), state(2)]),
+            Err(JopsChainError::UnpairedStates(3))
+        ));
+        assert!(matches!(
+            validate_jops_chain(&[state(0), state(2), state(3), state(1)]),
+            Err(JopsChainError::Decreasing { slice: 1, .. })
+        ));
+        assert!(matches!(
+            validate_jops_chain(&[state(0), state(2), state(3), state(5)]),
+            Err(JopsChainError::CarryOverMismatch { slice: 1, outgoing, incoming })
+                if outgoing == BigUint::from(2u64) && incoming == BigUint::from(3u64)
+        ));
+    }
+
+    #[test]
+    fn test_validate_context_ordering() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let chip = etable_chip();
+
+        let validate = |rule: fn(u32, u32) -> bool| {
+            chip.validate_context_ordering(
+                &traced.slice.itable,
+                &traced.event_table,
+                PreState(&traced.slice.initialization_state),
+                rule,
+            )
+        };
+
+        // The program reads and writes no context, both indices stay at zero.
+        assert!(validate(|input, output| input <= output).is_ok());
+
+        let err = validate(|input, output| input < output).unwrap_err();
+        assert_eq!(err.eid, traced.slice.etable.entries()[0].eid);
+        assert_eq!(err.context_in_index, 0);
+        assert_eq!(err.context_out_index, 0);
+    }
+
+    #[test]
+    fn test_maximal_memory_pages_mismatch() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let maximal_memory_pages = traced.slice.configure_table.maximal_memory_pages;
+        let error = Mutex::new(None);
+
+        let circuit = CheckedStateCircuit::new(traced, &error);
+        MockProver::run(MIN_K, &circuit, vec![vec![]]).unwrap();
+        assert!(error.lock().unwrap().is_none());
+
+        // The configure table disagrees with both states.
+        let circuit = CheckedStateCircuit {
+            configure_table: ConfigureTable {
+                maximal_memory_pages: maximal_memory_pages + 1,
+                ..traced.slice.configure_table
+            },
+            ..CheckedStateCircuit::new(traced, &error)
+        };
+        assert!(MockProver::run(MIN_K, &circuit, vec![vec![]]).is_err());
+        assert!(matches!(
+            *error.lock().unwrap(),
+            Some(EtableError::MaximalMemoryPagesMismatch { pre, post, configured })
+                if pre == maximal_memory_pages
+                    && post == maximal_memory_pages
+                    && configured == maximal_memory_pages + 1
+        ));
+
+        // The post state disagrees with the pre state.
+        let post_initialization_state = InitializationState {
+            maximal_memory_pages: maximal_memory_pages + 1,
+            ..(*traced.slice.post_initialization_state).clone()
+        };
+        let circuit = CheckedStateCircuit {
+            post_initialization_state: &post_initialization_state,
+            ..CheckedStateCircuit::new(traced, &error)
+        };
+        assert!(MockProver::run(MIN_K, &circuit, vec![vec![]]).is_err());
+        assert!(matches!(
+            *error.lock().unwrap(),
+            Some(EtableError::MaximalMemoryPagesMismatch { post, .. })
+                if post == maximal_memory_pages + 1
+        ));
+    }
+}
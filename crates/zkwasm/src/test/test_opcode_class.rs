@@ -0,0 +1,77 @@
+mod tests {
+    use specs::host_function::HostPlugin;
+    use specs::itable::OpcodeClass;
+    use specs::itable::OpcodeClassPlain;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_opcode_class_names() {
+        let expected = [
+            (OpcodeClass::LocalGet as usize, "local_get"),
+            (OpcodeClass::LocalSet as usize, "local_set"),
+            (OpcodeClass::LocalTee as usize, "local_tee"),
+            (OpcodeClass::GlobalGet as usize, "global_get"),
+            (OpcodeClass::GlobalSet as usize, "global_set"),
+            (OpcodeClass::Const as usize, "const"),
+            (OpcodeClass::Drop as usize, "drop"),
+            (OpcodeClass::Select as usize, "select"),
+            (OpcodeClass::Return as usize, "return"),
+            (OpcodeClass::Bin as usize, "bin"),
+            (OpcodeClass::Unary as usize, "unary"),
+            (OpcodeClass::BinShift as usize, "bin_shift"),
+            (OpcodeClass::BinBit as usize, "bin_bit"),
+            (OpcodeClass::Test as usize, "test"),
+            (OpcodeClass::Rel as usize, "rel"),
+            (OpcodeClass::Br as usize, "br"),
+            (OpcodeClass::BrIf as usize, "br_if"),
+            (OpcodeClass::BrIfEqz as usize, "br_if_eqz"),
+            (OpcodeClass::BrTable as usize, "br_table"),
+            (OpcodeClass::Unreachable as usize, "unreachable"),
+            (OpcodeClass::Call as usize, "call"),
+            (OpcodeClass::CallHost as usize, "call_host"),
+            (OpcodeClass::CallIndirect as usize, "call_indirect"),
+            (OpcodeClass::Load as usize, "load"),
+            (OpcodeClass::Store as usize, "store"),
+            (OpcodeClass::MemorySize as usize, "memory_size"),
+            (OpcodeClass::MemoryGrow as usize, "memory_grow"),
+            (OpcodeClass::Conversion as usize, "conversion"),
+            (
+                OpcodeClass::ForeignPluginStart as usize + HostPlugin::HostInput as usize,
+                "foreign_host_input",
+            ),
+            (
+                OpcodeClass::ForeignPluginStart as usize + HostPlugin::Context as usize,
+                "foreign_context",
+            ),
+            (
+                OpcodeClass::ForeignPluginStart as usize + HostPlugin::Require as usize,
+                "foreign_require",
+            ),
+        ];
+
+        for (index, name) in expected {
+            let class = OpcodeClassPlain(index);
+
+            assert_eq!(class.as_str(), name);
+            assert_eq!(name.parse::<OpcodeClassPlain>(), Ok(class));
+        }
+
+        assert!("foreign_plugin_start".parse::<OpcodeClassPlain>().is_err());
+    }
+
+    #[test]
+    fn test_opcode_class_json_keys() {
+        let histogram = BTreeMap::from([
+            (OpcodeClassPlain(OpcodeClass::Drop as usize), 1usize),
+            (OpcodeClassPlain(OpcodeClass::Store as usize), 2usize),
+        ]);
+
+        let json = serde_json::to_string(&histogram).unwrap();
+        assert_eq!(json, r#"{"drop":1,"store":2}"#);
+
+        let decoded: BTreeMap<OpcodeClassPlain, usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, histogram);
+
+        assert!(serde_json::from_str::<BTreeMap<OpcodeClassPlain, usize>>(r#"{"nop":1}"#).is_err());
+    }
+}
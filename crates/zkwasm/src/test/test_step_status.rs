@@ -0,0 +1,338 @@
+mod tests {
+    use num_bigint::BigUint;
+    use specs::configure_table::ConfigureTable;
+    use specs::etable::EventTableBackend;
+    use specs::etable::EventTableEntry;
+    use specs::itable::InstructionTable;
+    use specs::itable::InstructionTableInternal;
+    use specs::state::InitializationState;
+    use specs::state::InitializationStateBuilder;
+    use specs::step::StepInfo;
+    use specs::TraceBackend;
+
+    use crate::circuits::config::MIN_K;
+
+    use crate::circuits::etable::assign::PostState;
+    use crate::circuits::etable::assign::PreState;
+    use crate::circuits::utils::step_status::status_sequence_to_csv;
+    use crate::circuits::utils::step_status::status_to_init_state;
+    use crate::circuits::utils::step_status::verify_against_reference;
+    use crate::circuits::utils::step_status::DivergenceReport;
+    use crate::circuits::utils::step_status::ReferenceStep;
+    use crate::circuits::utils::step_status::Status;
+    use crate::circuits::utils::table_entry::EventTableEntryWithMemoryInfo;
+    use crate::loader::ZkWasmLoader;
+    use crate::runtime::host::default_env::DefaultHostEnvBuilder;
+    use crate::runtime::host::default_env::ExecutionArg;
+    use crate::runtime::host::HostEnvBuilder;
+    use crate::runtime::monitor::table_monitor::TableMonitor;
+    use crate::test::etable_chip;
+    use crate::test::trace_slices;
+    use crate::test::TEXTUAL_REPR_WITH_MEMORY;
+
+    #[test]
+    fn test_status_to_init_state() {
+        let itable = InstructionTable::new(InstructionTableInternal::default());
+
+        let status = Status {
+            eid: 100,
+            fid: 101,
+            iid: 102,
+            sp: 103,
+            last_jump_eid: 104,
+            allocated_memory_pages: 105,
+
+            rest_mops: 106,
+            jops: BigUint::from(107u64),
+
+            host_public_inputs: 108,
+            context_in_index: 109,
+            context_out_index: 110,
+            external_host_call_call_index: 111,
+
+            itable: &itable,
+        };
+
+        let entry = EventTableEntryWithMemoryInfo {
+            eentry: EventTableEntry {
+                eid: 1,
+                fid: 2,
+                iid: 3,
+                sp: 4,
+                allocated_memory_pages: 5,
+                last_jump_eid: 6,
+                step_info: StepInfo::Drop,
+            },
+            memory_rw_entires: vec![],
+        };
+
+        let configure_table = ConfigureTable {
+            init_memory_pages: 7,
+            maximal_memory_pages: 8,
+        };
+
+        let state = status_to_init_state(&status, &entry, &configure_table);
+
+        assert_eq!(state.eid, 1);
+        assert_eq!(state.fid, 2);
+        assert_eq!(state.iid, 3);
+        assert_eq!(state.sp, 4);
+        assert_eq!(state.frame_id, 6);
+
+        assert_eq!(state.host_public_inputs, 108);
+        assert_eq!(state.context_in_index, 109);
+        assert_eq!(state.context_out_index, 110);
+        assert_eq!(state.external_host_call_call_index, 111);
+
+        assert_eq!(state.initial_memory_pages, 5);
+        assert_eq!(state.maximal_memory_pages, 8);
+
+        #[cfg(feature = "continuation")]
+        assert_eq!(state.jops, BigUint::from(107u64));
+    }
+
+    #[test]
+    fn test_frame_id_follows_call_and_return() {
+        let textual_repr = r#"
+        (module
+            (func $callee
+              i32.const 0
+              drop
+            )
+
+            (func (export "test")
+              call $callee
+              i32.const 1
+              drop
+            )
+           )
+        "#;
+
+        let wasm = wabt::wat2wasm(&textual_repr).expect("failed to parse wat");
+        let module = ZkWasmLoader::parse_module(&wasm).unwrap();
+
+        let env = DefaultHostEnvBuilder.create_env(
+            MIN_K,
+            ExecutionArg {
+                public_inputs: vec![],
+                private_inputs: vec![],
+                context_inputs: vec![],
+            },
+        );
+        let mut monitor = TableMonitor::new(MIN_K, &vec![], TraceBackend::Memory, &env);
+        let mut loader = ZkWasmLoader::new(MIN_K, env).unwrap();
+        loader.set_entry("test".to_string());
+
+        let runner = loader.compile(&module, &mut monitor).unwrap();
+        loader.run(runner, &mut monitor).unwrap();
+
+        let tables = monitor.into_tables();
+        let itable = &tables.compilation_tables.itable;
+        let configure_table = &tables.compilation_tables.configure_table;
+        let event_table = match &tables.execution_tables.etable[0] {
+            EventTableBackend::Memory(event_table) => event_table,
+            EventTableBackend::Json(_) => unreachable!(),
+        };
+
+        // The eid of the call entering each active frame, 0 for the exported function.
+        let mut frames = vec![0];
+        let mut call_eid = None;
+
+        for eentry in event_table.entries() {
+            let status = Status {
+                eid: eentry.eid,
+                fid: eentry.fid,
+                iid: eentry.iid,
+                sp: eentry.sp,
+                last_jump_eid: eentry.last_jump_eid,
+                allocated_memory_pages: eentry.allocated_memory_pages,
+
+                rest_mops: 0,
+                jops: BigUint::from(0u64),
+
+                host_public_inputs: 0,
+                context_in_index: 0,
+                context_out_index: 0,
+                external_host_call_call_index: 0,
+
+                itable,
+            };
+            let entry = EventTableEntryWithMemoryInfo {
+                eentry: eentry.clone(),
+                memory_rw_entires: vec![],
+            };
+
+            let state = status_to_init_state(&status, &entry, configure_table);
+
+            assert_eq!(
+                state.frame_id,
+                *frames.last().unwrap(),
+                "eid {}",
+                eentry.eid
+            );
+
+            match eentry.step_info {
+                StepInfo::Call { .. } => {
+                    call_eid = Some(eentry.eid);
+                    frames.push(eentry.eid);
+                }
+                StepInfo::Return { .. } => {
+                    frames.pop();
+                }
+                _ => (),
+            }
+        }
+
+        let call_eid = call_eid.unwrap();
+        assert_ne!(call_eid, 0);
+        assert!(event_table
+            .entries()
+            .iter()
+            .any(|entry| entry.last_jump_eid == call_eid));
+    }
+
+    #[test]
+    fn test_is_genesis() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let slice = &traced[0].slice;
+        let configure_table = &slice.configure_table;
+
+        assert!(slice.initialization_state.is_genesis(
+            configure_table.init_memory_pages,
+            configure_table.maximal_memory_pages
+        ));
+
+        // The state after the execution has moved on.
+        assert!(!slice.post_initialization_state.is_genesis(
+            configure_table.init_memory_pages,
+            configure_table.maximal_memory_pages
+        ));
+
+        // Another memory configuration than the one the execution started with.
+        assert!(!slice.initialization_state.is_genesis(
+            configure_table.init_memory_pages + 1,
+            configure_table.maximal_memory_pages
+        ));
+    }
+
+    #[test]
+    fn test_initialization_state_builder() {
+        let state = InitializationStateBuilder::new()
+            .eid(1)
+            .fid(2)
+            .iid(3)
+            .frame_id(4)
+            .sp(5)
+            .host_public_inputs(6)
+            .context_in_index(7)
+            .context_out_index(8)
+            .external_host_call_call_index(9)
+            .initial_memory_pages(10)
+            .maximal_memory_pages(11)
+            .jops(BigUint::from(12u64))
+            .build();
+
+        assert_eq!(state.eid, 1);
+        assert_eq!(state.fid, 2);
+        assert_eq!(state.iid, 3);
+        assert_eq!(state.frame_id, 4);
+        assert_eq!(state.sp, 5);
+        assert_eq!(state.host_public_inputs, 6);
+        assert_eq!(state.context_in_index, 7);
+        assert_eq!(state.context_out_index, 8);
+        assert_eq!(state.external_host_call_call_index, 9);
+        assert_eq!(state.initial_memory_pages, 10);
+        assert_eq!(state.maximal_memory_pages, 11);
+        #[cfg(feature = "continuation")]
+        assert_eq!(state.jops, BigUint::from(12u64));
+
+        // Fields left out default to zero.
+        assert_eq!(
+            InitializationStateBuilder::new().build(),
+            InitializationState::default()
+        );
+    }
+
+    #[test]
+    fn test_verify_against_reference() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+
+        let status = etable_chip()
+            .status_sequence(
+                &traced.slice.itable,
+                &traced.event_table,
+                PreState(&traced.slice.initialization_state),
+                PostState(&traced.slice.post_initialization_state),
+            )
+            .unwrap();
+
+        let reference = traced
+            .slice
+            .etable
+            .entries()
+            .iter()
+            .map(|entry| ReferenceStep {
+                eid: entry.eid,
+                sp: entry.sp,
+                fid: entry.fid,
+                iid: entry.iid,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(verify_against_reference(&status, &reference), Ok(()));
+
+        let mut diverged = reference.clone();
+        diverged[1].sp += 1;
+        assert_eq!(
+            verify_against_reference(&status, &diverged),
+            Err(DivergenceReport::Field {
+                eid: reference[1].eid,
+                field: "sp",
+                status: reference[1].sp,
+                reference: reference[1].sp + 1,
+            })
+        );
+
+        let truncated = &reference[..reference.len() - 1];
+        assert_eq!(
+            verify_against_reference(&status, truncated),
+            Err(DivergenceReport::Length {
+                steps: reference.len(),
+                reference_steps: reference.len() - 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_status_sequence_to_csv() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+
+        let status = etable_chip()
+            .status_sequence(
+                &traced.slice.itable,
+                &traced.event_table,
+                PreState(&traced.slice.initialization_state),
+                PostState(&traced.slice.post_initialization_state),
+            )
+            .unwrap();
+
+        let csv = status_sequence_to_csv(&status);
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        // A header followed by a row per status, the terminal one included.
+        assert_eq!(lines.len(), status.len() + 1);
+        assert!(lines[0].starts_with("eid,fid,iid,sp,"));
+        for (line, status) in lines[1..].iter().zip(status.iter()) {
+            let columns = line.split(',').collect::<Vec<_>>();
+
+            assert_eq!(columns.len(), 12);
+            assert_eq!(columns[0], status.eid.to_string());
+            assert_eq!(columns[3], status.sp.to_string());
+        }
+
+        // Nothing but the header.
+        assert_eq!(status_sequence_to_csv(&[]), lines[0]);
+    }
+}
@@ -0,0 +1,852 @@
+mod tests {
+    use halo2_proofs::pairing::bn256::Fr;
+    use sha2::Sha256;
+    use specs::configure_table::ConfigureTable;
+    use specs::etable::EventTable;
+    use specs::itable::InstructionTable;
+    use specs::itable::InstructionTableInternal;
+    use specs::mtable::AccessType;
+    use specs::mtable::LocationType;
+    use specs::mtable::MTable;
+    use specs::mtable::MemoryTableEntry;
+    use specs::mtable::VarType;
+    use specs::step::StepInfo;
+
+    use crate::circuits::config::MIN_K;
+    use crate::circuits::image_table::PAGE_ENTRIES;
+    use crate::circuits::utils::interval_index::IntervalIndex;
+    use crate::circuits::utils::table_entry::call_frames_to_dot;
+    use crate::circuits::utils::table_entry::location_sort_key;
+    use crate::circuits::utils::table_entry::BoundaryMismatch;
+    use crate::circuits::utils::table_entry::EventTableEntries;
+    use crate::circuits::utils::table_entry::EventTableWithMemoryInfo;
+    use crate::circuits::utils::table_entry::MemoryWritingTable;
+    use crate::circuits::utils::table_entry::ReadPolicy;
+    use crate::error::MTableError;
+    use crate::runtime::memory_event_of_step;
+    use crate::test::trace_slices;
+    use crate::test::TEXTUAL_REPR_READ_BEFORE_WRITE;
+    use crate::test::TEXTUAL_REPR_WITH_MEMORY;
+
+    #[test]
+    fn test_location_sort_key() {
+        assert_eq!(location_sort_key(LocationType::Stack, 7), (1, 7));
+        assert_eq!(location_sort_key(LocationType::Heap, 7), (2, 7));
+        assert_eq!(location_sort_key(LocationType::Global, 7), (3, 7));
+    }
+
+    fn write(eid: u32, ltype: LocationType, offset: u32, value: u64) -> MemoryTableEntry {
+        MemoryTableEntry {
+            eid,
+            offset,
+            ltype,
+            atype: AccessType::Write,
+            vtype: VarType::I64,
+            is_mutable: true,
+            value,
+        }
+    }
+
+    fn memory_writing_table(entries: Vec<MemoryTableEntry>) -> MemoryWritingTable {
+        MemoryWritingTable::from_with_common_range(1 << 20, MTable::from(entries))
+    }
+
+    #[test]
+    fn test_canonicalize_coalesces_identical_writes() {
+        let table = memory_writing_table(vec![
+            MemoryTableEntry {
+                atype: AccessType::Init,
+                ..write(0, LocationType::Global, 0, 7)
+            },
+            write(5, LocationType::Global, 0, 7),
+            write(1, LocationType::Stack, 0, 5),
+            write(3, LocationType::Stack, 0, 5),
+            write(2, LocationType::Heap, 0, 1),
+            write(4, LocationType::Heap, 0, 2),
+        ]);
+
+        let original = table.sorted_by_eid();
+        assert_eq!(original.len(), 6);
+        let stack_end_eid = original[3].end_eid;
+
+        let canonical = table.canonicalize();
+        let entries = canonical.sorted_by_eid();
+
+        // The back-to-back stack writes collapse, the heap writes differ in value and the global
+        // write keeps apart from the init entry holding the same value.
+        assert_eq!(entries.len(), 5);
+        assert_eq!(entries[0].entry.eid, 0);
+        assert_eq!(entries[0].entry.atype, AccessType::Init);
+        assert_eq!(entries[0].end_eid, 5);
+        assert_eq!(entries[1].entry.eid, 1);
+        assert_eq!(entries[1].entry.ltype, LocationType::Stack);
+        assert_eq!(entries[1].end_eid, stack_end_eid);
+        assert_eq!(entries[2].entry.eid, 2);
+        assert_eq!(entries[2].end_eid, 4);
+        assert_eq!(entries[3].entry.eid, 4);
+        assert_eq!(entries[4].entry.eid, 5);
+        assert_eq!(entries[4].entry.atype, AccessType::Write);
+
+        // Canonicalizing is idempotent.
+        assert_eq!(canonical.canonicalize().sorted_by_eid().len(), 5);
+    }
+
+    #[test]
+    fn test_group_by_location() {
+        let table = memory_writing_table(vec![
+            write(2, LocationType::Stack, 0, 1),
+            write(3, LocationType::Stack, 0, 2),
+            write(4, LocationType::Heap, 0, 1),
+            write(1, LocationType::Global, 0, 1),
+        ]);
+
+        let groups = table
+            .group_by_location()
+            .unwrap()
+            .map(|(location, entries)| (location, entries.len()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            groups,
+            vec![
+                ((LocationType::Stack, 0), 2),
+                ((LocationType::Heap, 0), 1),
+                ((LocationType::Global, 0), 1),
+            ]
+        );
+
+        // The stack reappears after the heap.
+        let ungrouped = memory_writing_table(vec![
+            write(1, LocationType::Stack, 0, 1),
+            write(2, LocationType::Heap, 0, 1),
+            write(3, LocationType::Stack, 1, 1),
+        ]);
+
+        assert!(matches!(
+            ungrouped.group_by_location().err(),
+            Some(MTableError::UngroupedLocation {
+                ltype: LocationType::Stack,
+                offset: 1,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parallel_memory_writing_table_matches_sequential() {
+        let mut seed = 0x2545f491u64;
+        let mut next = |bound: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+
+            (seed >> 33) % bound
+        };
+
+        let mut entries = vec![];
+        for ltype in [
+            LocationType::Stack,
+            LocationType::Heap,
+            LocationType::Global,
+        ] {
+            for offset in 0..500 {
+                let mut eid = 0;
+
+                entries.push(MemoryTableEntry {
+                    atype: AccessType::Init,
+                    ..write(0, ltype, offset, next(100))
+                });
+
+                for _ in 0..next(64) {
+                    // A step may access the same location twice.
+                    eid += next(3) as u32;
+
+                    let entry = write(eid, ltype, offset, next(100));
+                    entries.push(if next(2) == 0 {
+                        MemoryTableEntry {
+                            atype: AccessType::Read,
+                            ..entry
+                        }
+                    } else {
+                        entry
+                    });
+                }
+            }
+        }
+
+        let mtable = MTable::from(entries);
+
+        let parallel = MemoryWritingTable::from_with_common_range(1 << 20, mtable.clone());
+        let sequential = MemoryWritingTable::from_with_common_range_sequential(1 << 20, mtable);
+
+        assert_eq!(
+            serde_json::to_string(&parallel).unwrap(),
+            serde_json::to_string(&sequential).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_interval_index_boundaries() {
+        let table = memory_writing_table(vec![
+            write(1, LocationType::Stack, 0, 5),
+            write(3, LocationType::Stack, 0, 6),
+        ]);
+        let index = IntervalIndex::new(&table);
+
+        let point = |eid| {
+            index
+                .query_point(LocationType::Stack, 0, eid)
+                .map(|entry| entry.entry.eid)
+        };
+
+        // A read observes the writes of the previous steps, including one at the eid of the
+        // next write, as the memory info of the event table is resolved.
+        assert_eq!(point(1), None);
+        assert_eq!(point(2), Some(1));
+        assert_eq!(point(3), Some(1));
+        assert_eq!(point(4), Some(3));
+        assert!(!table.covers_read(LocationType::Stack, 0, 1));
+        assert!(table.covers_read(LocationType::Stack, 0, 3));
+
+        assert_eq!(
+            index
+                .query_write(LocationType::Stack, 0, 3)
+                .map(|entry| entry.entry.value),
+            Some(6)
+        );
+        assert!(index.query_write(LocationType::Stack, 0, 2).is_none());
+
+        let range = |start_eid, end_eid| {
+            index
+                .query_range(LocationType::Stack, 0, start_eid, end_eid)
+                .iter()
+                .map(|entry| entry.entry.eid)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(range(0, 1), Vec::<u32>::new());
+        assert_eq!(range(0, 2), vec![1]);
+        assert_eq!(range(2, 3), vec![1]);
+        assert_eq!(range(3, 4), vec![3]);
+
+        assert_eq!(
+            table
+                .entries_crossing(3)
+                .iter()
+                .map(|entry| entry.entry.eid)
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_read_policy() {
+        let traced = trace_slices(TEXTUAL_REPR_READ_BEFORE_WRITE);
+        let traced = &traced[0];
+
+        // All reads are covered by the memory table of the trace.
+        let resolved = EventTableWithMemoryInfo::with_read_policy(
+            &traced.slice.etable,
+            &traced.memory_writing_table,
+            ReadPolicy::Strict,
+        )
+        .unwrap();
+        assert!(resolved.synthetic_writes().is_empty());
+        assert_eq!(
+            resolved.into_assignable().unwrap().len(),
+            traced.slice.etable.entries().len()
+        );
+
+        // Without the init entry of the global, its first read is covered by no write.
+        let mtable = traced.slice.create_memory_table(memory_event_of_step);
+        let without_init = MemoryWritingTable::from(
+            MIN_K,
+            MTable::from(
+                mtable
+                    .entries()
+                    .iter()
+                    .filter(|entry| {
+                        !(entry.ltype == LocationType::Global && entry.atype == AccessType::Init)
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            ),
+        );
+
+        assert!(EventTableWithMemoryInfo::with_read_policy(
+            &traced.slice.etable,
+            &without_init,
+            ReadPolicy::Strict,
+        )
+        .is_err());
+
+        let resolved = EventTableWithMemoryInfo::with_read_policy(
+            &traced.slice.etable,
+            &without_init,
+            ReadPolicy::Lenient,
+        )
+        .unwrap();
+        assert_eq!(resolved.synthetic_writes().len(), 1);
+        assert_eq!(resolved.synthetic_writes()[0].ltype, LocationType::Global);
+        assert_eq!(resolved.synthetic_writes()[0].value, 0);
+        assert!(resolved.into_assignable().is_none());
+    }
+
+    #[test]
+    fn test_boundary_diff() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let table = &traced[0].memory_writing_table;
+
+        // Against its own initial memory, the global set by the program is reported.
+        assert!(table.boundary_diff(table).contains(&BoundaryMismatch {
+            ltype: LocationType::Global,
+            offset: 0,
+            finalized_value: 2,
+            initial_value: 0,
+        }));
+
+        let finalized = memory_writing_table(vec![
+            write(1, LocationType::Stack, 0, 5),
+            write(2, LocationType::Stack, 1, 6),
+        ]);
+        let init = |offset, value| MemoryTableEntry {
+            atype: AccessType::Init,
+            ..write(0, LocationType::Stack, offset, value)
+        };
+
+        // The next slice starts from the finalized values, the untouched location is skipped.
+        assert!(finalized
+            .boundary_diff(&memory_writing_table(vec![init(0, 5)]))
+            .is_empty());
+
+        assert_eq!(
+            finalized.boundary_diff(&memory_writing_table(vec![init(0, 5), init(1, 7)])),
+            vec![BoundaryMismatch {
+                ltype: LocationType::Stack,
+                offset: 1,
+                finalized_value: 6,
+                initial_value: 7,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_finalizing_event() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+
+        let set_global_eid = traced
+            .slice
+            .etable
+            .entries()
+            .iter()
+            .find(|entry| matches!(entry.step_info, StepInfo::SetGlobal { .. }))
+            .unwrap()
+            .eid;
+
+        assert_eq!(
+            traced
+                .memory_writing_table
+                .finalizing_event(LocationType::Global, 0),
+            Some(set_global_eid)
+        );
+        // The program has a single global.
+        assert_eq!(
+            traced
+                .memory_writing_table
+                .finalizing_event(LocationType::Global, 1),
+            None
+        );
+
+        // An initialized location which is never written.
+        let table = memory_writing_table(vec![MemoryTableEntry {
+            atype: AccessType::Init,
+            ..write(0, LocationType::Global, 0, 1)
+        }]);
+        assert_eq!(table.finalizing_event(LocationType::Global, 0), None);
+    }
+
+    #[test]
+    fn test_split_by_location() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let table = &traced[0].memory_writing_table;
+
+        let partitions = table.split_by_location();
+
+        // The program touches the stack, the memory and its global.
+        assert_eq!(
+            partitions.keys().cloned().collect::<Vec<_>>(),
+            vec![
+                LocationType::Stack,
+                LocationType::Heap,
+                LocationType::Global
+            ]
+        );
+        assert_eq!(
+            partitions
+                .values()
+                .map(|partition| partition.sorted_by_eid().len())
+                .sum::<usize>(),
+            table.sorted_by_eid().len()
+        );
+        for (ltype, partition) in &partitions {
+            assert!(partition
+                .sorted_by_eid()
+                .iter()
+                .all(|entry| entry.entry.ltype == *ltype));
+        }
+
+        // Sub tables keep the lifespans of the whole table.
+        let table = memory_writing_table(vec![
+            write(1, LocationType::Stack, 0, 1),
+            write(3, LocationType::Stack, 0, 2),
+        ]);
+        let partitions = table.split_by_location();
+
+        assert_eq!(
+            partitions.keys().cloned().collect::<Vec<_>>(),
+            vec![LocationType::Stack]
+        );
+        let entries = partitions[&LocationType::Stack].sorted_by_eid();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.entry.eid, entry.end_eid))
+                .collect::<Vec<_>>(),
+            table
+                .sorted_by_eid()
+                .iter()
+                .map(|entry| (entry.entry.eid, entry.end_eid))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(entries[0].end_eid, 3);
+
+        assert!(memory_writing_table(vec![]).split_by_location().is_empty());
+    }
+
+    #[test]
+    fn test_exclude_constant_locations() {
+        let table = memory_writing_table(vec![
+            MemoryTableEntry {
+                atype: AccessType::Init,
+                ..write(0, LocationType::Global, 0, 1)
+            },
+            write(1, LocationType::Stack, 0, 1),
+            write(2, LocationType::Stack, 0, 2),
+            write(3, LocationType::Heap, 0, 1),
+        ]);
+
+        // Only the stack location is written more than once.
+        let mutated = table.exclude_constant_locations();
+        let entries = mutated.sorted_by_eid();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.entry.ltype, entry.entry.eid))
+                .collect::<Vec<_>>(),
+            vec![(LocationType::Stack, 1), (LocationType::Stack, 2)]
+        );
+        assert_eq!(entries[0].end_eid, 2);
+
+        // Without mutated locations, nothing is left.
+        let constant = memory_writing_table(vec![
+            write(1, LocationType::Stack, 0, 1),
+            write(2, LocationType::Heap, 0, 1),
+        ]);
+        assert!(constant
+            .exclude_constant_locations()
+            .sorted_by_eid()
+            .is_empty());
+
+        // The global of the traced program is initialized then set.
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let mutated = traced[0].memory_writing_table.exclude_constant_locations();
+        assert!(mutated
+            .sorted_by_eid()
+            .iter()
+            .any(|entry| entry.entry.ltype == LocationType::Global));
+        let entries = mutated.sorted_by_eid();
+        for entry in &entries {
+            assert!(
+                entries
+                    .iter()
+                    .filter(|other| other.entry.ltype == entry.entry.ltype
+                        && other.entry.offset == entry.entry.offset)
+                    .count()
+                    > 1
+            );
+        }
+    }
+
+    #[test]
+    fn test_entries_crossing() {
+        let table = memory_writing_table(vec![
+            write(1, LocationType::Stack, 0, 5),
+            write(3, LocationType::Stack, 0, 6),
+            write(2, LocationType::Heap, 0, 1),
+        ]);
+
+        let crossing = |boundary_eid| {
+            table
+                .entries_crossing(boundary_eid)
+                .iter()
+                .map(|entry| (entry.entry.ltype, entry.entry.eid))
+                .collect::<Vec<_>>()
+        };
+
+        // No write precedes the boundary, including the one at the boundary itself.
+        assert_eq!(crossing(0), vec![]);
+        assert_eq!(crossing(1), vec![]);
+
+        assert_eq!(crossing(2), vec![(LocationType::Stack, 1)]);
+        assert_eq!(
+            crossing(3),
+            vec![(LocationType::Stack, 1), (LocationType::Heap, 2)]
+        );
+        assert_eq!(
+            crossing(4),
+            vec![(LocationType::Stack, 3), (LocationType::Heap, 2)]
+        );
+
+        // At the end of the trace, the last write of every location carries its final value.
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let crossing = traced
+            .memory_writing_table
+            .entries_crossing(traced.slice.post_initialization_state.eid);
+
+        assert!(crossing
+            .iter()
+            .any(|entry| entry.entry.ltype == LocationType::Global && entry.entry.value == 2));
+        assert!(crossing
+            .iter()
+            .all(|entry| entry.entry.eid < traced.slice.post_initialization_state.eid));
+    }
+
+    #[test]
+    fn test_commitment() {
+        let entries = vec![
+            write(1, LocationType::Stack, 0, 5),
+            write(3, LocationType::Stack, 0, 6),
+            write(2, LocationType::Heap, 0, 1),
+        ];
+        let commitment = memory_writing_table(entries.clone()).commitment::<Sha256>();
+
+        // Deterministic over the same entries.
+        assert_eq!(
+            memory_writing_table(entries.clone()).commitment::<Sha256>(),
+            commitment
+        );
+
+        // Any field of an entry, or its lifespan, changes the commitment.
+        let mut changed_value = entries.clone();
+        changed_value[2].value = 2;
+        assert_ne!(
+            memory_writing_table(changed_value).commitment::<Sha256>(),
+            commitment
+        );
+
+        let mut changed_lifespan = entries.clone();
+        changed_lifespan[1].eid = 4;
+        assert_ne!(
+            memory_writing_table(changed_lifespan).commitment::<Sha256>(),
+            commitment
+        );
+
+        assert_ne!(
+            memory_writing_table(entries[..2].to_vec()).commitment::<Sha256>(),
+            commitment
+        );
+
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        assert_eq!(
+            traced[0].memory_writing_table.commitment::<Sha256>(),
+            trace_slices(TEXTUAL_REPR_WITH_MEMORY)[0]
+                .memory_writing_table
+                .commitment::<Sha256>()
+        );
+    }
+
+    #[test]
+    fn test_verify_instructions_present() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+
+        assert!(traced
+            .event_table
+            .verify_instructions_present(&traced.slice.itable)
+            .is_ok());
+
+        // Every step is missing from an empty instruction table.
+        let missing = traced
+            .event_table
+            .verify_instructions_present(
+                &InstructionTable::new(InstructionTableInternal::default()),
+            )
+            .unwrap_err();
+        assert_eq!(
+            missing
+                .iter()
+                .map(|missing| missing.eid)
+                .collect::<Vec<_>>(),
+            traced
+                .slice
+                .etable
+                .entries()
+                .iter()
+                .map(|entry| entry.eid)
+                .collect::<Vec<_>>()
+        );
+
+        // The other program has fewer functions and instructions.
+        let other = trace_slices(TEXTUAL_REPR_READ_BEFORE_WRITE);
+        assert!(traced
+            .event_table
+            .verify_instructions_present(&other[0].slice.itable)
+            .is_err());
+    }
+
+    #[test]
+    fn test_max_concurrent_live() {
+        // The first stack write ends when the second one starts, the heap write is live since 2.
+        let table = memory_writing_table(vec![
+            write(1, LocationType::Stack, 0, 5),
+            write(3, LocationType::Stack, 0, 6),
+            write(2, LocationType::Heap, 0, 1),
+        ]);
+        assert_eq!(table.max_concurrent_live(), (2, 2));
+
+        let table = memory_writing_table(vec![
+            write(1, LocationType::Stack, 0, 5),
+            write(2, LocationType::Heap, 0, 1),
+            write(3, LocationType::Global, 0, 1),
+        ]);
+        assert_eq!(table.max_concurrent_live(), (3, 3));
+
+        assert_eq!(memory_writing_table(vec![]).max_concurrent_live(), (0, 0));
+
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let table = &traced[0].memory_writing_table;
+        let (_, live) = table.max_concurrent_live();
+        assert!(live > 0);
+        assert!(live <= table.sorted_by_eid().len());
+    }
+
+    #[test]
+    fn test_hottest_locations() {
+        let table = memory_writing_table(vec![
+            write(1, LocationType::Stack, 0, 1),
+            write(2, LocationType::Stack, 0, 2),
+            write(3, LocationType::Stack, 0, 3),
+            write(4, LocationType::Stack, 1, 1),
+            MemoryTableEntry {
+                atype: AccessType::Init,
+                ..write(0, LocationType::Heap, 0, 0)
+            },
+            write(5, LocationType::Heap, 0, 1),
+            write(6, LocationType::Heap, 0, 2),
+        ]);
+
+        // The init entry is not counted as a write.
+        assert_eq!(
+            table.hottest_locations(2).unwrap(),
+            vec![((LocationType::Stack, 0), 3), ((LocationType::Heap, 0), 2)]
+        );
+        assert!(table.hottest_locations(0).unwrap().is_empty());
+
+        let ungrouped = memory_writing_table(vec![
+            write(1, LocationType::Stack, 0, 1),
+            write(2, LocationType::Heap, 0, 1),
+            write(3, LocationType::Stack, 1, 1),
+        ]);
+        assert!(ungrouped.hottest_locations(2).is_err());
+
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let hottest = traced[0].memory_writing_table.hottest_locations(3).unwrap();
+        assert!(!hottest.is_empty() && hottest.len() <= 3);
+        assert!(hottest.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+
+    #[test]
+    fn test_validate_against_config() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let table = &traced.memory_writing_table;
+
+        assert!(table
+            .validate_against_config(&traced.slice.configure_table)
+            .is_ok());
+
+        // No page to write, every heap write is reported.
+        let violations = table
+            .validate_against_config(&ConfigureTable {
+                maximal_memory_pages: 0,
+                ..traced.slice.configure_table
+            })
+            .unwrap_err();
+        let heap_writes = table
+            .sorted_by_eid()
+            .into_iter()
+            .filter(|entry| {
+                entry.entry.ltype == LocationType::Heap && entry.entry.atype == AccessType::Write
+            })
+            .map(|entry| entry.entry.eid)
+            .collect::<Vec<_>>();
+        assert!(!heap_writes.is_empty());
+        assert_eq!(
+            violations
+                .iter()
+                .map(|violation| violation.eid)
+                .collect::<Vec<_>>(),
+            heap_writes
+        );
+        assert!(violations.iter().all(|violation| violation.bound == 0));
+
+        // The bound is exclusive.
+        let configure_table = ConfigureTable {
+            init_memory_pages: 1,
+            maximal_memory_pages: 1,
+        };
+        let table = memory_writing_table(vec![
+            write(1, LocationType::Heap, PAGE_ENTRIES - 1, 1),
+            write(2, LocationType::Heap, PAGE_ENTRIES, 1),
+        ]);
+        let violations = table.validate_against_config(&configure_table).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].offset, PAGE_ENTRIES);
+    }
+
+    #[test]
+    fn test_call_frames() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let entries = traced.slice.etable.entries();
+
+        let call_index = entries
+            .iter()
+            .position(|entry| matches!(entry.step_info, StepInfo::Call { .. }))
+            .unwrap();
+        let return_index = entries
+            .iter()
+            .position(|entry| matches!(entry.step_info, StepInfo::Return { .. }))
+            .unwrap();
+        let call_eid = entries[call_index].eid;
+        let inc_fid = entries[call_index + 1].fid;
+
+        // The exported function calls $inc once.
+        let frames = traced.event_table.call_frames();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].frame_id, 0);
+        assert_eq!(frames[0].entry_range, 0..entries.len());
+        assert_eq!(frames[0].children.len(), 1);
+
+        let callee = &frames[0].children[0];
+        assert_eq!(callee.frame_id, call_eid);
+        assert_eq!(callee.fid, inc_fid);
+        assert_eq!(callee.entry_range, call_index + 1..return_index + 1);
+        assert!(callee.children.is_empty());
+
+        assert!(
+            call_frames_to_dot(&frames).contains(&format!("f{} -> f{}", frames[0].fid, inc_fid))
+        );
+
+        // Starting within $inc, the slice returns to a caller it never entered.
+        let sub_table = EventTable::new(entries[call_index + 1..].to_vec());
+        let frames =
+            EventTableWithMemoryInfo::new(&sub_table, &traced.memory_writing_table).call_frames();
+        assert_eq!(
+            frames
+                .iter()
+                .map(|frame| (frame.frame_id, frame.entry_range.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (call_eid, 0..return_index - call_index),
+                (0, return_index - call_index..entries.len() - call_index - 1),
+            ]
+        );
+        assert!(!call_frames_to_dot(&frames).contains("->"));
+    }
+
+    #[test]
+    fn test_validate_sp_bounds() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let entries = traced.slice.etable.entries();
+
+        let min_sp = entries.iter().map(|entry| entry.sp).min().unwrap();
+        let max_sp = entries.iter().map(|entry| entry.sp).max().unwrap();
+        assert!(min_sp < max_sp);
+
+        assert!(traced
+            .event_table
+            .validate_sp_bounds(min_sp, max_sp)
+            .is_ok());
+
+        // The first step reaching the deepest stack is reported.
+        let deepest = entries.iter().find(|entry| entry.sp == max_sp).unwrap();
+        let violation = traced
+            .event_table
+            .validate_sp_bounds(min_sp, max_sp - 1)
+            .unwrap_err();
+        assert_eq!(violation.eid, deepest.eid);
+        assert_eq!(violation.sp, max_sp);
+
+        let shallowest = entries.iter().find(|entry| entry.sp == min_sp).unwrap();
+        let violation = traced
+            .event_table
+            .validate_sp_bounds(min_sp + 1, max_sp)
+            .unwrap_err();
+        assert_eq!(violation.eid, shallowest.eid);
+        assert_eq!(violation.sp, min_sp);
+    }
+
+    #[test]
+    fn test_write_density() {
+        let table = memory_writing_table(vec![
+            MemoryTableEntry {
+                atype: AccessType::Init,
+                ..write(0, LocationType::Stack, 0, 0)
+            },
+            write(0, LocationType::Stack, 1, 1),
+            write(1, LocationType::Stack, 2, 1),
+            write(5, LocationType::Stack, 3, 1),
+            write(9, LocationType::Stack, 4, 1),
+            write(12, LocationType::Stack, 5, 1),
+        ]);
+
+        // The init entry is not a write, the one beyond the eids falls in the last bucket.
+        assert_eq!(table.write_density(2, 10), vec![2, 3]);
+        assert_eq!(table.write_density(5, 10), vec![2, 0, 1, 0, 2]);
+        assert!(table.write_density(0, 10).is_empty());
+
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        let traced = &traced[0];
+        let writes = traced
+            .memory_writing_table
+            .sorted_by_eid()
+            .iter()
+            .filter(|entry| entry.entry.atype == AccessType::Write)
+            .count();
+        let density = traced
+            .memory_writing_table
+            .write_density(4, traced.slice.post_initialization_state.eid);
+
+        assert_eq!(density.len(), 4);
+        assert_eq!(density.iter().sum::<u32>() as usize, writes);
+    }
+
+    #[test]
+    fn test_validate_values_fit_field() {
+        let traced = trace_slices(TEXTUAL_REPR_WITH_MEMORY);
+        assert!(traced[0]
+            .memory_writing_table
+            .validate_values_fit_field::<Fr>()
+            .is_ok());
+
+        // Any u64 is below the modulus of a 254 bits field.
+        let table = memory_writing_table(vec![
+            write(1, LocationType::Stack, 0, u64::MAX),
+            write(2, LocationType::Heap, 0, 0),
+        ]);
+        assert!(table.validate_values_fit_field::<Fr>().is_ok());
+    }
+}